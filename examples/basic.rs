@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use dioxus_mosaic::{Mosaic, MosaicBuilder, tile};
+use dioxus_mosaic::{tile, use_persistent_layout, Mosaic, MosaicBuilder, TileKind, TileRegistry};
 
 fn main() {
     dioxus::launch(App);
@@ -8,45 +8,36 @@ fn main() {
 #[component]
 fn App() -> Element {
     // Create a simple 3-panel layout: sidebar | (editor / terminal)
-    let mut layout = use_signal(|| {
+    // The layout is restored from localStorage and saved back on every change
+    let mut workspace = use_persistent_layout("dioxus-mosaic-basic", || {
         MosaicBuilder::horizontal()
             .left(tile("sidebar"))
             .right(
                 MosaicBuilder::vertical()
                     .top(tile("editor"))
                     .bottom(tile("terminal"))
-                    .split(70.0)  // 70% editor, 30% terminal
-                    .build_tree()
+                    .split(70.0) // 70% editor, 30% terminal
+                    .build_tree(),
             )
-            .split(25.0)  // 25% sidebar, 75% main area
+            .split(25.0) // 25% sidebar, 75% main area
             .build()
     });
 
-    // Render functions need to be boxed and wrapped in signals
-    let render_tile = use_signal(|| {
-        Box::new(move |tile_id: String| {
-            match tile_id.as_str() {
-                "sidebar" => Some(rsx! { SidebarPanel {} }),
-                "editor" => Some(rsx! { EditorPanel {} }),
-                "terminal" => Some(rsx! { TerminalPanel {} }),
-                _ => None
-            }
-        }) as Box<dyn Fn(String) -> Option<Element>>
-    });
-
-    let render_title = use_signal(|| {
-        Box::new(move |tile_id: String| {
-            rsx! {
-                span {
-                    match tile_id.as_str() {
-                        "sidebar" => "Files",
-                        "editor" => "Editor",
-                        "terminal" => "Terminal",
-                        _ => "Unknown"
-                    }
-                }
-            }
-        }) as Box<dyn Fn(String) -> Element>
+    // Each kind of tile registers its renderer and title once
+    let registry = use_hook(|| {
+        TileRegistry::new()
+            .register(
+                "sidebar",
+                TileKind::new(|_| rsx! { SidebarPanel {} }).title("Files"),
+            )
+            .register(
+                "editor",
+                TileKind::new(|_| rsx! { EditorPanel {} }).title("Editor"),
+            )
+            .register(
+                "terminal",
+                TileKind::new(|_| rsx! { TerminalPanel {} }).title("Terminal"),
+            )
     });
 
     rsx! {
@@ -54,12 +45,12 @@ fn App() -> Element {
 
         div { class: "app",
             h1 { class: "title", "dioxus-mosaic - Basic Example" }
+            button { onclick: move |_| workspace.reset(), "Reset layout" }
 
             div { class: "mosaic-container",
                 Mosaic {
-                    layout: layout,
-                    render_tile: render_tile,
-                    render_title: render_title,
+                    layout: workspace.layout(),
+                    registry: registry,
                 }
             }
         }
@@ -103,7 +94,7 @@ fn EditorPanel() -> Element {
                     "\n"
                     "#[component]\n"
                     "fn App() -> Element {{\n"
-                    "    let mut layout = use_signal(|| {{\n"
+                    "    let layout = use_signal(|| {{\n"
                     "        MosaicBuilder::horizontal()\n"
                     "            .left(tile(\"sidebar\"))\n"
                     "            .right(tile(\"editor\"))\n"
@@ -1,4 +1,5 @@
 use crate::layout::MosaicLayout;
+use crate::node::TileCapabilities;
 use crate::tree_api::MosaicNode;
 use crate::types::{SplitDirection, TileId};
 
@@ -111,6 +112,19 @@ pub fn tile(tile_id: impl Into<TileId>) -> MosaicNode {
     MosaicNode::Leaf(tile_id.into())
 }
 
+/// Create a tile node with the given capabilities (helper function)
+///
+/// # Example
+/// ```ignore
+/// let layout = MosaicBuilder::horizontal()
+///     .left(tile_with("sidebar", TileCapabilities::locked().draggable(false)))
+///     .right(tile("editor"))
+///     .build();
+/// ```
+pub fn tile_with(tile_id: impl Into<TileId>, capabilities: TileCapabilities) -> MosaicNode {
+    tile(tile_id).with_capabilities(capabilities)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,7 +137,10 @@ mod tests {
             .split(40.0)
             .build();
 
-        assert_eq!(layout.get_all_tiles(), vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["a".to_string(), "b".to_string()]
+        );
     }
 
     #[test]
@@ -134,7 +151,10 @@ mod tests {
             .split(60.0)
             .build();
 
-        assert_eq!(layout.get_all_tiles(), vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["a".to_string(), "b".to_string()]
+        );
     }
 
     #[test]
@@ -160,4 +180,22 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_builder_capabilities() {
+        let layout = MosaicBuilder::horizontal()
+            .left(tile_with(
+                "sidebar",
+                TileCapabilities::default().closable(false),
+            ))
+            .right(tile("editor").locked())
+            .build();
+
+        let sidebar = layout.tile_capabilities(&"sidebar".to_string()).unwrap();
+        assert!(!sidebar.closable && sidebar.drop_target);
+        assert!(layout
+            .tile_capabilities(&"editor".to_string())
+            .unwrap()
+            .is_locked());
+    }
 }
@@ -0,0 +1,222 @@
+use crate::drag_drop::{CanDrop, DragPayload};
+use crate::drop_zone::DropZone;
+use crate::layout::MosaicLayout;
+use crate::types::{MosaicMode, NodeId, TileId};
+use dioxus::prelude::*;
+
+/// Layout rendered by `Mosaic`, and where user interactions send their changes
+///
+/// Every layout change made from inside the mosaic (resizing, dropping,
+/// closing, splitting) goes through [`LayoutController::update`], which:
+/// - writes it to the layout signal when the state is owned by `Mosaic` or by
+///   the caller's `Signal<MosaicLayout>`
+/// - only proposes it through `on_change` in strictly controlled mode (`value`),
+///   leaving the parent to accept, modify or reject it
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct LayoutController {
+    /// Where the layout comes from and where changes go, following the props
+    /// of `Mosaic`
+    binding: ReadSignal<LayoutBinding>,
+    mode: Option<Memo<MosaicMode>>,
+    /// Split whose divider is being dragged, and its current position
+    resizing: Signal<Option<(NodeId, f64)>>,
+}
+
+/// Layout a [`LayoutController`] renders, and what it does with changes
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct LayoutBinding {
+    layout: ReadSignal<MosaicLayout>,
+    /// Signal changes are written to, `None` in strictly controlled mode
+    writable: Option<Signal<MosaicLayout>>,
+    on_change: Option<Callback<MosaicLayout>>,
+    can_drop: Option<CanDrop>,
+}
+
+impl LayoutBinding {
+    /// Write changes to `layout`, then report them to `on_change`
+    pub(crate) fn signal(
+        layout: Signal<MosaicLayout>,
+        on_change: Option<Callback<MosaicLayout>>,
+    ) -> Self {
+        Self {
+            layout: ReadSignal::new(layout),
+            writable: Some(layout),
+            on_change,
+            can_drop: None,
+        }
+    }
+
+    /// Render `value` and only propose changes to `on_change`
+    pub(crate) fn controlled(
+        value: Memo<MosaicLayout>,
+        on_change: Option<Callback<MosaicLayout>>,
+    ) -> Self {
+        Self {
+            layout: ReadSignal::new(value),
+            writable: None,
+            on_change,
+            can_drop: None,
+        }
+    }
+
+    /// Also ask `can_drop` (the `Mosaic` prop) before dropping anything
+    pub(crate) fn with_can_drop(mut self, can_drop: Option<CanDrop>) -> Self {
+        self.can_drop = can_drop;
+        self
+    }
+}
+
+impl LayoutController {
+    /// Controller following `binding`, which may change while it is in use
+    pub(crate) fn new(binding: impl Into<ReadSignal<LayoutBinding>>) -> Self {
+        Self {
+            binding: binding.into(),
+            mode: None,
+            resizing: Signal::new(None),
+        }
+    }
+
+    /// Controller writing changes to `layout`, then reporting them to `on_change`
+    #[cfg(test)]
+    pub(crate) fn signal(
+        layout: Signal<MosaicLayout>,
+        on_change: Option<Callback<MosaicLayout>>,
+    ) -> Self {
+        Self::new(Signal::new(LayoutBinding::signal(layout, on_change)))
+    }
+
+    /// Controller rendering `value` and only proposing changes to `on_change`
+    #[cfg(test)]
+    pub(crate) fn controlled(
+        value: Memo<MosaicLayout>,
+        on_change: Option<Callback<MosaicLayout>>,
+    ) -> Self {
+        Self::new(Signal::new(LayoutBinding::controlled(value, on_change)))
+    }
+
+    /// Follow `mode` (the `Mosaic` prop): nothing is dropped in read-only mode
+    pub(crate) fn with_mode(mut self, mode: Memo<MosaicMode>) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Whether users can't change the layout right now (`MosaicMode::ReadOnly`)
+    pub(crate) fn is_read_only(&self) -> bool {
+        self.mode.is_some_and(|mode| mode.read().is_read_only())
+    }
+
+    /// Whether changes are only proposed to `on_change` (strictly controlled mode)
+    pub(crate) fn is_controlled(&self) -> bool {
+        self.binding.read().writable.is_none()
+    }
+
+    /// The layout being rendered
+    pub(crate) fn layout(&self) -> ReadSignal<MosaicLayout> {
+        self.binding.read().layout
+    }
+
+    /// The layout with the divider being dragged at its current position, if any
+    ///
+    /// Tiles follow the divider while it is dragged, the layout itself only
+    /// changes when it is released (see [`LayoutController::resize`]).
+    pub(crate) fn resized_layout(&self) -> Option<MosaicLayout> {
+        let (split_id, position) = self.resizing.read().clone()?;
+        let mut layout = self.layout().read().clone();
+        layout.update_split(&split_id, position);
+        Some(layout)
+    }
+
+    /// Move the divider of `split_id` to `position` while it is being dragged
+    pub(crate) fn resize_live(mut self, split_id: &NodeId, position: f64) {
+        self.resizing.set(Some((split_id.clone(), position)));
+    }
+
+    /// Stop showing a drag of the divider of `split_id` (e.g. once it is unmounted)
+    pub(crate) fn cancel_resize(mut self, split_id: &NodeId) {
+        let dragged = self
+            .resizing
+            .try_peek()
+            .is_ok_and(|resizing| resizing.as_ref().is_some_and(|(id, _)| id == split_id));
+        if dragged {
+            self.resizing.set(None);
+        }
+    }
+
+    /// Release the divider of `split_id` at `position`, submitting the change
+    pub(crate) fn resize(mut self, split_id: &NodeId, position: f64) -> bool {
+        self.resizing.set(None);
+        self.update(|layout| layout.update_split(split_id, position))
+    }
+
+    /// Whether `payload` may be dropped in `zone` of `target`, or along the edges
+    /// of the layout when `target` is `None`
+    ///
+    /// Checks both the layout's own rules (`MosaicLayout::can_drop`) and the
+    /// `can_drop` prop, and refuses everything in read-only mode.
+    pub(crate) fn can_drop(
+        &self,
+        payload: &DragPayload,
+        target: Option<&TileId>,
+        zone: DropZone,
+    ) -> bool {
+        if self.is_read_only() {
+            return false;
+        }
+        let binding = *self.binding.read();
+        let layout = binding.layout.read();
+        let allowed = match payload {
+            DragPayload::Split(node_id) => layout.can_move_node(node_id, target, zone),
+            _ => layout.can_drop(&payload.tile_id(&layout), target, zone),
+        };
+        allowed
+            && binding.can_drop.is_none_or(|can_drop| {
+                payload
+                    .tile_ids(&layout)
+                    .into_iter()
+                    .all(|tile_id| can_drop.call((tile_id, target.cloned(), zone)))
+            })
+    }
+
+    /// Whether `payload` may be inserted at the boundary of the split `split_id`
+    ///
+    /// The `can_drop` prop is asked for both tiles next to the boundary. Splits
+    /// can't be dropped on dividers.
+    pub(crate) fn can_drop_at_split(&self, payload: &DragPayload, split_id: &NodeId) -> bool {
+        if self.is_read_only() || matches!(payload, DragPayload::Split(_)) {
+            return false;
+        }
+        let binding = *self.binding.read();
+        let layout = binding.layout.read();
+        let tile_id = payload.tile_id(&layout);
+        if !layout.can_drop_at_split(&tile_id, split_id) {
+            return false;
+        }
+        match (binding.can_drop, layout.split_neighbours(split_id)) {
+            (Some(can_drop), Some(neighbours)) => neighbours
+                .into_iter()
+                .all(|(target, zone)| can_drop.call((tile_id.clone(), Some(target), zone))),
+            _ => true,
+        }
+    }
+
+    /// Apply `change` to a copy of the layout and submit the result
+    ///
+    /// Nothing is written or reported when `change` leaves the layout as is.
+    /// Returns whatever `change` returned, e.g. whether the operation succeeded.
+    pub(crate) fn update<R>(&self, change: impl FnOnce(&mut MosaicLayout) -> R) -> R {
+        let binding = *self.binding.peek();
+        let mut proposed = binding.layout.peek().clone();
+        let result = change(&mut proposed);
+        if proposed == *binding.layout.peek() {
+            return result;
+        }
+
+        if let Some(mut layout) = binding.writable {
+            layout.set(proposed.clone());
+        }
+        if let Some(on_change) = binding.on_change {
+            on_change.call(proposed);
+        }
+        result
+    }
+}
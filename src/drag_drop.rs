@@ -1,49 +1,96 @@
-use crate::types::TileId;
+use crate::controller::LayoutController;
+use crate::drop_zone::{DropZone, DropZoneGeometry};
+use crate::layout::MosaicLayout;
+use crate::registry::TileRegistry;
+use crate::theme::use_theme_style;
+use crate::types::{NodeId, TileId};
 use dioxus::prelude::*;
-use serde::{Deserialize, Serialize};
-
-/// Drop zone position when hovering over a tile
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum DropZone {
-    /// Top 25% of tile (creates vertical split with dragged tile on top)
-    Top,
-    /// Bottom 25% of tile (creates vertical split with dragged tile on bottom)
-    Bottom,
-    /// Left 25% of tile (creates horizontal split with dragged tile on left)
-    Left,
-    /// Right 25% of tile (creates horizontal split with dragged tile on right)
-    Right,
-}
-
-impl DropZone {
-    /// Get the split direction for this drop zone
-    pub fn split_direction(&self) -> crate::types::SplitDirection {
+
+/// What is being dragged over the mosaic
+#[derive(Clone, Debug, PartialEq)]
+pub enum DragPayload {
+    /// A tile that is already in the layout
+    Tile(TileId),
+    /// A tile to open with this ID when dropped
+    NewTile(TileId),
+    /// A tile of this registry kind to open when dropped, with an ID like `kind:1`
+    Kind(String),
+    /// A split of the layout, moved with all its tiles
+    ///
+    /// Only dropped in the mosaic it comes from, e.g. dragged by its grab handle.
+    Split(NodeId),
+}
+
+impl DragPayload {
+    /// ID to show while dragging (e.g. to render the ghost's title)
+    pub fn display_id(&self) -> &str {
         match self {
-            DropZone::Top | DropZone::Bottom => crate::types::SplitDirection::Vertical,
-            DropZone::Left | DropZone::Right => crate::types::SplitDirection::Horizontal,
+            DragPayload::Tile(tile_id) | DragPayload::NewTile(tile_id) => tile_id,
+            DragPayload::Kind(kind) => kind,
+            DragPayload::Split(node_id) => node_id,
         }
     }
 
-    /// Whether the dragged tile should be the first child in the split
-    pub fn dragged_is_first(&self) -> bool {
+    /// ID of the tile the payload becomes once dropped in `layout`
+    ///
+    /// Kinds get the first `kind:<n>` ID that isn't in the layout yet, and
+    /// splits give their first tile.
+    pub fn tile_id(&self, layout: &MosaicLayout) -> TileId {
         match self {
-            DropZone::Top | DropZone::Left => true,
-            DropZone::Bottom | DropZone::Right => false,
+            DragPayload::Tile(tile_id) | DragPayload::NewTile(tile_id) => tile_id.clone(),
+            DragPayload::Kind(kind) => (1..)
+                .map(|n| format!("{kind}:{n}"))
+                .find(|tile_id| layout.find_tile(tile_id).is_none())
+                .unwrap_or_default(),
+            DragPayload::Split(node_id) => layout
+                .subtree_tiles(node_id)
+                .into_iter()
+                .next()
+                .unwrap_or_default(),
         }
     }
+
+    /// IDs of all the tiles the payload puts in `layout` once dropped
+    pub fn tile_ids(&self, layout: &MosaicLayout) -> Vec<TileId> {
+        match self {
+            DragPayload::Split(node_id) => layout.subtree_tiles(node_id),
+            _ => vec![self.tile_id(layout)],
+        }
+    }
+}
+
+/// Where the dragged payload would land if it was dropped now
+#[derive(Clone, Debug, PartialEq)]
+pub enum DropTarget {
+    /// A drop zone of a tile
+    Tile(TileId, DropZone),
+    /// An edge of the whole layout, to dock the payload there
+    Edge(DropZone),
+    /// The divider of a split, to insert the payload between its panes
+    Divider(NodeId),
 }
 
 /// Global drag state
 #[derive(Clone, Default, PartialEq)]
 pub struct DragState {
-    /// ID of the tile currently being dragged
+    /// ID of the tile currently being dragged, if it is already in the layout
     pub dragging_tile_id: Option<TileId>,
 
+    /// What is being dragged: a tile of the layout or an external item
+    pub payload: Option<DragPayload>,
+
     /// Current mouse/cursor position during drag
     pub drag_position: (f64, f64),
 
     /// Currently hovered target tile and drop zone
     pub hover_target: Option<(TileId, DropZone)>,
+
+    /// Currently hovered drop target: a tile (as in `hover_target`), an edge
+    /// of the layout or a divider
+    pub drop_target: Option<DropTarget>,
+
+    /// Mosaic the dragged tile comes from, to move it between mosaics
+    pub(crate) source: Option<LayoutController>,
 }
 
 impl DragState {
@@ -52,13 +99,71 @@ impl DragState {
     }
 
     pub fn is_dragging(&self) -> bool {
-        self.dragging_tile_id.is_some()
+        self.payload.is_some()
     }
 
     pub fn start_drag(&mut self, tile_id: TileId, x: f64, y: f64) {
-        self.dragging_tile_id = Some(tile_id);
+        self.start_payload_drag(DragPayload::Tile(tile_id), x, y);
+    }
+
+    /// Start dragging any payload, e.g. a new tile from outside the mosaic
+    pub fn start_payload_drag(&mut self, payload: DragPayload, x: f64, y: f64) {
+        self.dragging_tile_id = match &payload {
+            DragPayload::Tile(tile_id) => Some(tile_id.clone()),
+            _ => None,
+        };
+        self.payload = Some(payload);
         self.drag_position = (x, y);
-        self.hover_target = None;
+        self.clear_hover();
+        self.source = None;
+    }
+
+    /// Start dragging a tile of the mosaic whose layout is behind `source`
+    pub(crate) fn start_tile_drag(
+        &mut self,
+        source: LayoutController,
+        tile_id: TileId,
+        x: f64,
+        y: f64,
+    ) {
+        self.start_drag(tile_id, x, y);
+        self.source = Some(source);
+    }
+
+    /// Start dragging a split of the mosaic whose layout is behind `source`
+    pub(crate) fn start_split_drag(
+        &mut self,
+        source: LayoutController,
+        node_id: NodeId,
+        x: f64,
+        y: f64,
+    ) {
+        self.start_payload_drag(DragPayload::Split(node_id), x, y);
+        self.source = Some(source);
+    }
+
+    /// Payload the mosaic of `controller` can take: splits only move inside their
+    /// own mosaic, and tiles don't move in or out of strictly controlled ones
+    pub(crate) fn payload_for(&self, controller: LayoutController) -> Option<&DragPayload> {
+        self.payload
+            .as_ref()
+            .filter(|payload| !is_refused_move(controller, payload, self.source))
+    }
+
+    /// Whether `tile_id` of the mosaic of `controller` is dragged, alone or in a split
+    pub(crate) fn drags_tile(&self, controller: LayoutController, tile_id: &TileId) -> bool {
+        match &self.payload {
+            Some(DragPayload::Tile(dragged)) => dragged == tile_id,
+            Some(DragPayload::Split(node_id)) => {
+                self.source == Some(controller)
+                    && controller
+                        .layout()
+                        .read()
+                        .subtree_tiles(node_id)
+                        .contains(tile_id)
+            }
+            _ => false,
+        }
     }
 
     pub fn update_position(&mut self, x: f64, y: f64) {
@@ -66,149 +171,768 @@ impl DragState {
     }
 
     pub fn update_hover(&mut self, tile_id: TileId, zone: DropZone) {
-        self.hover_target = Some((tile_id, zone));
+        self.hover_target = Some((tile_id.clone(), zone));
+        self.drop_target = Some(DropTarget::Tile(tile_id, zone));
+    }
+
+    /// Hover an edge of the layout
+    pub fn hover_edge(&mut self, zone: DropZone) {
+        self.hover_target = None;
+        self.drop_target = Some(DropTarget::Edge(zone));
+    }
+
+    /// Hover the divider of the split `split_id`
+    pub fn hover_divider(&mut self, split_id: NodeId) {
+        self.hover_target = None;
+        self.drop_target = Some(DropTarget::Divider(split_id));
     }
 
     pub fn clear_hover(&mut self) {
         self.hover_target = None;
+        self.drop_target = None;
     }
 
     pub fn end_drag(&mut self) {
         self.dragging_tile_id = None;
+        self.payload = None;
         self.drag_position = (0.0, 0.0);
-        self.hover_target = None;
+        self.clear_hover();
+        self.source = None;
     }
 }
 
-/// Calculate which drop zone the cursor is in based on position within target element
+/// Apply the drop of `payload` next to `target`, or along the edges of the layout
 ///
-/// Returns None if in the center (no-drop zone)
-pub fn calculate_drop_zone(
-    mouse_x: f64,
-    mouse_y: f64,
-    rect_x: f64,
-    rect_y: f64,
-    rect_width: f64,
-    rect_height: f64,
-) -> Option<DropZone> {
-    // Calculate relative position (0.0 to 1.0)
-    let rel_x = ((mouse_x - rect_x) / rect_width).clamp(0.0, 1.0);
-    let rel_y = ((mouse_y - rect_y) / rect_height).clamp(0.0, 1.0);
-
-    // Define drop zone margins (30% from each edge for better UX)
-    const MARGIN: f64 = 0.3;
-
-    // Check edge zones (priority: edges over center)
-    if rel_y < MARGIN {
-        Some(DropZone::Top)
-    } else if rel_y > 1.0 - MARGIN {
-        Some(DropZone::Bottom)
-    } else if rel_x < MARGIN {
-        Some(DropZone::Left)
-    } else if rel_x > 1.0 - MARGIN {
-        Some(DropZone::Right)
-    } else {
-        // Center zone - no drop
-        None
+/// Drops that `LayoutController::can_drop` refuses are ignored. A tile dragged
+/// from another mosaic (`source`) is transferred from its layout, unless either
+/// mosaic is strictly controlled. New and transferred tiles are only opened if
+/// the registry allows it. Returns whether the layout changed (or, in strictly
+/// controlled mode, a change was proposed).
+pub(crate) fn drop_payload(
+    controller: LayoutController,
+    registry: Option<TileRegistry>,
+    payload: &DragPayload,
+    source: Option<LayoutController>,
+    target: Option<&TileId>,
+    zone: DropZone,
+) -> bool {
+    if is_refused_move(controller, payload, source) || !controller.can_drop(payload, target, zone) {
+        return false;
     }
+    match transfer_source(controller, payload, source) {
+        Some(source) => {
+            let mut from = source.layout().peek().clone();
+            let transferred = controller.update(|to| {
+                apply_drop(
+                    to,
+                    Some(&mut from),
+                    registry.as_ref(),
+                    payload,
+                    target,
+                    zone,
+                )
+            });
+            if transferred {
+                source.update(|layout| *layout = from);
+            }
+            transferred
+        }
+        None => controller
+            .update(|layout| apply_drop(layout, None, registry.as_ref(), payload, target, zone)),
+    }
+}
+
+/// Apply the drop of `payload` on the divider of the split `split_id`
+///
+/// Inserts the tile between the split's panes with `insert_at_split`. Tiles
+/// from other mosaics can't be dropped on dividers.
+pub(crate) fn drop_payload_at_split(
+    controller: LayoutController,
+    registry: Option<TileRegistry>,
+    payload: &DragPayload,
+    source: Option<LayoutController>,
+    split_id: &NodeId,
+) -> bool {
+    if transfer_source(controller, payload, source).is_some()
+        || is_refused_move(controller, payload, source)
+        || !controller.can_drop_at_split(payload, split_id)
+    {
+        return false;
+    }
+    controller.update(|layout| apply_drop_at_split(layout, registry.as_ref(), payload, split_id))
+}
+
+/// Layout the mosaic of `controller` would get if `payload` was dropped on the
+/// divider of `split_id` now
+///
+/// A dry run of `drop_payload_at_split`; returns None if the drop would be refused.
+pub(crate) fn preview_drop_at_split(
+    controller: LayoutController,
+    registry: Option<TileRegistry>,
+    payload: &DragPayload,
+    source: Option<LayoutController>,
+    split_id: &NodeId,
+) -> Option<MosaicLayout> {
+    if transfer_source(controller, payload, source).is_some()
+        || is_refused_move(controller, payload, source)
+        || !controller.can_drop_at_split(payload, split_id)
+    {
+        return None;
+    }
+    let mut layout = controller.layout().read().clone();
+    apply_drop_at_split(&mut layout, registry.as_ref(), payload, split_id).then_some(layout)
 }
 
-/// Get the CSS style for a drop zone overlay
-pub fn get_drop_zone_style(
+/// Layout the mosaic of `controller` would get if `payload` was dropped now
+///
+/// A dry run of `drop_payload` on a copy of the layout; returns None if the
+/// drop would be refused.
+pub(crate) fn preview_drop(
+    controller: LayoutController,
+    registry: Option<TileRegistry>,
+    payload: &DragPayload,
+    source: Option<LayoutController>,
+    target: Option<&TileId>,
     zone: DropZone,
-    is_active: bool,
-) -> String {
-    let (position_props, size_props) = match zone {
-        DropZone::Top => ("top: 0; left: 0; right: 0;", "height: 30%;"),
-        DropZone::Bottom => ("bottom: 0; left: 0; right: 0;", "height: 30%;"),
-        DropZone::Left => ("top: 0; bottom: 0; left: 0;", "width: 30%;"),
-        DropZone::Right => ("top: 0; bottom: 0; right: 0;", "width: 30%;"),
+) -> Option<MosaicLayout> {
+    if is_refused_move(controller, payload, source) || !controller.can_drop(payload, target, zone) {
+        return None;
+    }
+    let mut layout = controller.layout().read().clone();
+    let mut from =
+        transfer_source(controller, payload, source).map(|source| source.layout().peek().clone());
+    apply_drop(
+        &mut layout,
+        from.as_mut(),
+        registry.as_ref(),
+        payload,
+        target,
+        zone,
+    )
+    .then_some(layout)
+}
+
+/// Mosaic the dragged tile has to be transferred from, when it isn't the one of `controller`
+fn transfer_source(
+    controller: LayoutController,
+    payload: &DragPayload,
+    source: Option<LayoutController>,
+) -> Option<LayoutController> {
+    match payload {
+        DragPayload::Tile(_) => source.filter(|source| *source != controller),
+        _ => None,
+    }
+}
+
+/// Whether the mosaic of `controller` can't take `payload` from `source`
+///
+/// Node IDs only mean something in their own layout, so splits from other
+/// mosaics are refused. So are tiles moved in or out of a strictly controlled
+/// mosaic: a transfer changes two layouts, and its parent could reject one
+/// half of it, losing or duplicating the tile.
+fn is_refused_move(
+    controller: LayoutController,
+    payload: &DragPayload,
+    source: Option<LayoutController>,
+) -> bool {
+    match payload {
+        DragPayload::Split(_) => source != Some(controller),
+        _ => transfer_source(controller, payload, source)
+            .is_some_and(|source| controller.is_controlled() || source.is_controlled()),
+    }
+}
+
+/// Apply a drop on the divider of `split_id` to `layout`
+fn apply_drop_at_split(
+    layout: &mut MosaicLayout,
+    registry: Option<&TileRegistry>,
+    payload: &DragPayload,
+    split_id: &NodeId,
+) -> bool {
+    let tile_id = payload.tile_id(layout);
+    if let DragPayload::Tile(_) = payload {
+        return layout.insert_at_split(&tile_id, split_id);
+    }
+    if registry.is_some_and(|registry| !registry.can_open(layout, &tile_id)) {
+        return false;
+    }
+    layout.insert_new_tile_at_split(tile_id, split_id)
+}
+
+/// Apply a drop to `layout`, taking the tile out of `from` for a transfer
+fn apply_drop(
+    layout: &mut MosaicLayout,
+    from: Option<&mut MosaicLayout>,
+    registry: Option<&TileRegistry>,
+    payload: &DragPayload,
+    target: Option<&TileId>,
+    zone: DropZone,
+) -> bool {
+    let tile_id = payload.tile_id(layout);
+    let opened = from.is_some() || !matches!(payload, DragPayload::Tile(_) | DragPayload::Split(_));
+    if opened && registry.is_some_and(|registry| !registry.can_open(layout, &tile_id)) {
+        return false;
+    }
+
+    match (payload, from) {
+        (DragPayload::Split(node_id), _) => layout.move_node(node_id, target, zone),
+        (DragPayload::Tile(_), Some(from)) => {
+            MosaicLayout::transfer_tile(from, layout, &tile_id, target, zone)
+        }
+        (DragPayload::Tile(_), None) => match target {
+            Some(target) => layout.insert_tile_with_split(&tile_id, target, zone),
+            None => layout.dock_at_root(&tile_id, zone),
+        },
+        _ => match target {
+            Some(target) => layout.insert_new_tile(tile_id, target, zone),
+            None => layout.dock_new_tile_at_root(tile_id, zone),
+        },
+    }
+}
+
+/// Drag state shared by the mosaics and drag sources below a `MosaicDragProvider`
+#[derive(Clone, Copy)]
+pub(crate) struct SharedDragState(pub(crate) Signal<DragState>);
+
+/// Shares one drag state between everything it wraps
+///
+/// Needed for [`DragSource`]s outside of a `Mosaic`, e.g. in a sidebar palette,
+/// and to drag tiles from one `Mosaic` to another: wrap all of them.
+///
+/// Tiles dragged from a mosaic get their ghost from that mosaic; the provider
+/// draws the ghost of the other payloads.
+///
+/// # Example
+/// ```ignore
+/// MosaicDragProvider {
+///     aside {
+///         DragSource { payload: DragPayload::Kind("terminal".into()), "Terminal" }
+///     }
+///     Mosaic { layout: workspace, registry }
+///     Mosaic { layout: strip, registry }
+/// }
+/// ```
+#[component]
+pub fn MosaicDragProvider(children: Element) -> Element {
+    let drag_state = use_context_provider(|| SharedDragState(Signal::new(DragState::new()))).0;
+    let external = drag_state.read().source.is_none();
+
+    rsx! {
+        {children}
+        if external && drag_state.read().is_dragging() {
+            DragGhost {
+                drag_state,
+                title: drag_state.read().payload.as_ref().map(|payload| rsx! { "{payload.display_id()}" }),
+            }
+        }
+    }
+}
+
+/// Makes its children draggable into a `Mosaic`
+///
+/// Dropping a `NewTile` or `Kind` payload on a tile (or along the edges of the
+/// mosaic) opens a new tile there. Must be rendered below a [`MosaicDragProvider`]
+/// or inside a `Mosaic`.
+#[component]
+pub fn DragSource(payload: DragPayload, children: Element) -> Element {
+    let shared = try_use_context::<SharedDragState>();
+    let mosaic = try_use_context::<Signal<DragState>>();
+    let theme = use_theme_style();
+    let Some(mut drag_state) = shared.map(|shared| shared.0).or(mosaic) else {
+        dioxus::logger::tracing::warn!(
+            "DragSource needs a MosaicDragProvider or a Mosaic above it"
+        );
+        return rsx! { {children} };
     };
 
+    rsx! {
+        div {
+            class: "mosaic-drag-source",
+            draggable: "true",
+            ondragstart: move |evt| {
+                let position = evt.client_coordinates();
+                drag_state.write().start_payload_drag(payload.clone(), position.x, position.y);
+            },
+            ondrag: move |evt| {
+                let position = evt.client_coordinates();
+
+                // Drag events can fire at 0,0 when the drag ends
+                if position.x != 0.0 || position.y != 0.0 {
+                    drag_state.write().update_position(position.x, position.y);
+                }
+            },
+            ondragend: move |_evt| {
+                drag_state.write().end_drag();
+            },
+            style: if theme.styled { "cursor: grab; user-select: none;" } else { "" },
+            {children}
+        }
+    }
+}
+
+/// Name of a drop zone in class names
+pub(crate) fn zone_name(zone: DropZone) -> &'static str {
+    match zone {
+        DropZone::Top => "top",
+        DropZone::Bottom => "bottom",
+        DropZone::Left => "left",
+        DropZone::Right => "right",
+        DropZone::Center => "center",
+    }
+}
+
+/// Get the CSS style for a drop zone overlay, covering exactly the zone of `geometry`
+pub fn get_drop_zone_style(zone: DropZone, is_active: bool, geometry: &DropZoneGeometry) -> String {
+    overlay_style(&geometry.zone_css(zone), is_active)
+}
+
+/// Style of a drop zone overlay placed by `area` (CSS position and size)
+pub(crate) fn overlay_style(area: &str, is_active: bool) -> String {
     let bg_color = if is_active {
-        "rgba(59, 130, 246, 0.3)" // blue-500 with opacity
+        "var(--mosaic-drop-zone-active-background)"
     } else {
-        "rgba(59, 130, 246, 0.15)"
+        "var(--mosaic-drop-zone-background)"
     };
 
     let border_color = if is_active {
-        "rgba(59, 130, 246, 0.8)"
+        "var(--mosaic-drop-zone-active-border)"
     } else {
-        "rgba(59, 130, 246, 0.4)"
+        "var(--mosaic-drop-zone-border)"
     };
 
     format!(
-        "position: absolute; {}; {}; background-color: {}; border: 2px dashed {}; pointer-events: none; transition: all 0.15s ease; z-index: 10; border-radius: 4px;",
-        position_props, size_props, bg_color, border_color
+        "position: absolute; {} box-sizing: border-box; background-color: {}; border: 2px dashed {}; pointer-events: none; transition: all 0.15s ease; z-index: 10; border-radius: 4px;",
+        area, bg_color, border_color
     )
 }
 
+/// The `can_drop` prop of `Mosaic`: called with the dragged tile ID, the target
+/// tile (`None` for the edges of the whole mosaic) and the drop zone
+///
+/// A dragged split is asked about once for each of its tiles.
+pub type CanDrop = Callback<(TileId, Option<TileId>, DropZone), bool>;
+
+/// What `render_drag_preview` is given to draw the drag preview
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragPreview {
+    /// ID of the dragged tile (the kind for a `DragPayload::Kind`, the node ID for a `Split`)
+    pub tile_id: TileId,
+    /// What is being dragged
+    pub payload: DragPayload,
+    /// Tile and drop zone currently under the cursor
+    pub hover_target: Option<(TileId, DropZone)>,
+}
+
+/// How `Mosaic` draws the ghost following the cursor during a drag
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragPreviewOptions {
+    /// Distance in pixels from the cursor to the ghost's top-left corner
+    pub offset: (f64, f64),
+    /// Show a static copy of the dragged tile's content, as it was when the
+    /// drag started, scaled by this factor instead of the "Dragging..."
+    /// placeholder (web renderer only)
+    pub snapshot_scale: Option<f64>,
+}
+
+impl Default for DragPreviewOptions {
+    fn default() -> Self {
+        Self {
+            offset: (10.0, 10.0),
+            snapshot_scale: None,
+        }
+    }
+}
+
+impl DragPreviewOptions {
+    /// Move the ghost away from the cursor, e.g. so it doesn't cover the drop zones
+    pub fn offset(mut self, x: f64, y: f64) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Show a static copy of the dragged tile's content, scaled by `scale`
+    ///
+    /// The tile's DOM is cloned, so its components don't run a second time.
+    /// Other renderers than web have no DOM to copy and show an empty ghost.
+    pub fn snapshot(mut self, scale: f64) -> Self {
+        self.snapshot_scale = Some(scale);
+        self
+    }
+}
+
 /// Drag ghost component that follows the cursor
+///
+/// `title` is shown in the ghost's header, usually the dragged tile's title,
+/// and `content` below it. A `preview` replaces the whole ghost.
 #[component]
-pub fn DragGhost(drag_state: Signal<DragState>, render_title: Signal<Box<dyn Fn(TileId) -> Element>>) -> Element {
+pub fn DragGhost(
+    drag_state: Signal<DragState>,
+    title: Option<Element>,
+    content: Option<Element>,
+    preview: Option<Element>,
+    /// Distance in pixels from the cursor to the ghost's top-left corner
+    #[props(default = (10.0, 10.0))]
+    offset: (f64, f64),
+) -> Element {
+    let theme = use_theme_style();
+    let styled = theme.styled;
     let state = drag_state.read();
 
     // If not dragging, don't render anything
-    if state.dragging_tile_id.is_none() {
+    if !state.is_dragging() {
         return rsx! { div { style: "display: none;" } };
     }
 
-    let dragging_tile = state.dragging_tile_id.as_ref().unwrap().clone();
-
     let (x, y) = state.drag_position;
 
-    // Offset so ghost appears slightly below and to the right of cursor
-    let offset_x = x + 10.0;
-    let offset_y = y + 10.0;
+    // Offset so ghost appears below and to the right of cursor
+    let offset_x = x + offset.0;
+    let offset_y = y + offset.1;
 
-    let title = (render_title.read())(dragging_tile.clone());
+    if let Some(preview) = preview {
+        return rsx! {
+            div {
+                class: "drag-ghost drag-ghost-custom",
+                // The position follows the cursor, so it is emitted even when unstyled
+                style: "position: fixed; left: {offset_x}px; top: {offset_y}px; pointer-events: none; z-index: 9999;",
+                {preview}
+            }
+        };
+    }
 
     rsx! {
         div {
             class: "drag-ghost",
-            style: "
-                position: fixed;
-                left: {offset_x}px;
-                top: {offset_y}px;
-                width: 200px;
-                height: 120px;
-                background-color: #1a1d24;
-                border: 2px solid #3b82f6;
-                border-radius: 8px;
-                box-shadow: 0 8px 16px rgba(0, 0, 0, 0.3);
-                opacity: 0.85;
-                pointer-events: none;
-                z-index: 9999;
-                display: flex;
-                flex-direction: column;
-                overflow: hidden;
-            ",
+            style: if styled {
+                format!("
+                    {}
+                    position: fixed;
+                    left: {offset_x}px;
+                    top: {offset_y}px;
+                    width: 200px;
+                    height: 120px;
+                    background-color: var(--mosaic-tile-background);
+                    border: 2px solid var(--mosaic-accent);
+                    border-radius: var(--mosaic-tile-radius);
+                    box-shadow: var(--mosaic-shadow);
+                    opacity: 0.85;
+                    pointer-events: none;
+                    z-index: 9999;
+                    display: flex;
+                    flex-direction: column;
+                    overflow: hidden;
+                ", theme.variables)
+            } else {
+                // The position follows the cursor, so it is emitted even when unstyled
+                format!("left: {offset_x}px; top: {offset_y}px;")
+            },
 
             // Ghost header
             div {
-                style: "
+                class: "drag-ghost-header",
+                style: if styled {
+                    "
                     padding: 0.5rem 0.75rem;
-                    background-color: #14161c;
-                    border-bottom: 1px solid #2a2f3a;
+                    background-color: var(--mosaic-header-background);
+                    border-bottom: 1px solid var(--mosaic-tile-border);
                     font-size: 0.875rem;
                     font-weight: 600;
-                    color: #ffffff;
-                ",
+                    color: var(--mosaic-header-text);
+                    "
+                } else {
+                    ""
+                },
                 {title}
             }
 
             // Ghost content placeholder
             div {
-                style: "
+                class: "drag-ghost-content",
+                style: if styled {
+                    "
                     flex: 1;
                     display: flex;
                     align-items: center;
                     justify-content: center;
-                    color: #888;
+                    position: relative;
+                    overflow: hidden;
+                    color: var(--mosaic-muted-text);
                     font-size: 0.75rem;
-                ",
-                "Dragging..."
+                    "
+                } else {
+                    ""
+                },
+                if let Some(content) = content {
+                    {content}
+                } else {
+                    "Dragging..."
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{tile, MosaicBuilder};
+    use crate::registry::TileKind;
+
+    /// sidebar | (editor / terminal)
+    fn workspace() -> MosaicLayout {
+        MosaicBuilder::horizontal()
+            .left(tile("sidebar"))
+            .right(
+                MosaicBuilder::vertical()
+                    .top(tile("editor"))
+                    .bottom(tile("terminal"))
+                    .split(70.0)
+                    .build_tree(),
+            )
+            .split(25.0)
+            .build()
+    }
+
+    /// Run `test` in the scope of an empty app, so it can create signals
+    fn in_scope<R>(test: impl FnOnce() -> R) -> R {
+        let mut dom = VirtualDom::new(|| rsx! {});
+        dom.rebuild_in_place();
+        dom.in_scope(ScopeId::APP, test)
+    }
+
+    fn tiles(controller: LayoutController) -> Vec<TileId> {
+        controller.layout().read().get_all_tiles()
+    }
+
+    fn parent_split(controller: LayoutController, tile_id: &str) -> NodeId {
+        let layout = controller.layout();
+        let layout = layout.read();
+        layout
+            .find_tile(&tile_id.to_string())
+            .and_then(|id| layout.get_node(&id)?.parent().cloned())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_external_payloads_open_new_tiles() {
+        in_scope(|| {
+            let controller = LayoutController::signal(Signal::new(workspace()), None);
+            let registry = Some(
+                TileRegistry::new()
+                    .register("sidebar", TileKind::new(|_| rsx! {}).allow_multiple(false)),
+            );
+            let drop = |payload: DragPayload, target: Option<&str>| {
+                let target = target.map(|target| target.to_string());
+                drop_payload(
+                    controller,
+                    registry.clone(),
+                    &payload,
+                    None,
+                    target.as_ref(),
+                    DropZone::Right,
+                )
+            };
+            // Kinds get a fresh ID each time they're dropped
+            assert!(drop(
+                DragPayload::Kind("editor".to_string()),
+                Some("sidebar")
+            ));
+            assert!(drop(DragPayload::Kind("editor".to_string()), None));
+            assert!(drop(
+                DragPayload::NewTile("notes".to_string()),
+                Some("terminal")
+            ));
+            // The registry refuses a second sidebar, and tiles are only opened once
+            assert!(!drop(
+                DragPayload::NewTile("sidebar:2".to_string()),
+                Some("editor")
+            ));
+            assert!(!drop(
+                DragPayload::NewTile("notes".to_string()),
+                Some("editor")
+            ));
+            assert_eq!(
+                tiles(controller),
+                ["sidebar", "editor:1", "editor", "terminal", "notes", "editor:2"]
+            );
+        });
+    }
+
+    #[test]
+    fn test_tiles_move_between_layouts() {
+        in_scope(|| {
+            let workspace = LayoutController::signal(Signal::new(workspace()), None);
+            let strip = LayoutController::signal(
+                Signal::new(MosaicLayout::new("monitor".to_string())),
+                None,
+            );
+
+            // Moving out the last tile of the strip empties it...
+            let monitor = DragPayload::Tile("monitor".to_string());
+            assert!(drop_payload(
+                workspace,
+                None,
+                &monitor,
+                Some(strip),
+                Some(&"editor".to_string()),
+                DropZone::Right
+            ));
+            assert!(strip.layout().read().is_empty());
+            assert_eq!(
+                tiles(workspace),
+                ["sidebar", "editor", "monitor", "terminal"]
+            );
+
+            // ...and it still takes drops on its edges
+            let terminal = DragPayload::Tile("terminal".to_string());
+            assert!(drop_payload(
+                strip,
+                None,
+                &terminal,
+                Some(workspace),
+                None,
+                DropZone::Top
+            ));
+            assert_eq!(tiles(workspace), ["sidebar", "editor", "monitor"]);
+            assert_eq!(tiles(strip), ["terminal"]);
+        });
+    }
+
+    #[test]
+    fn test_tiles_dont_move_in_or_out_of_controlled_layouts() {
+        in_scope(|| {
+            let workspace_layout = Signal::new(workspace());
+            let strip_layout = Signal::new(MosaicLayout::new("monitor".to_string()));
+            let workspace = LayoutController::signal(workspace_layout, None);
+            let strip = LayoutController::signal(strip_layout, None);
+            let mut drag = DragState::new();
+            drag.start_tile_drag(workspace, "terminal".to_string(), 10.0, 10.0);
+            let payload = DragPayload::Tile("terminal".to_string());
+            let monitor = "monitor".to_string();
+
+            // A controlled strip whose parent ignores `on_change` can't take the tile...
+            let ignore = Callback::new(|_: MosaicLayout| {});
+            let controlled_strip = LayoutController::controlled(
+                Memo::new(move || strip_layout.cloned()),
+                Some(ignore),
+            );
+            assert!(drag.payload_for(controlled_strip).is_none());
+            assert!(preview_drop(
+                controlled_strip,
+                None,
+                &payload,
+                drag.source,
+                Some(&monitor),
+                DropZone::Bottom
+            )
+            .is_none());
+            assert!(!drop_payload(
+                controlled_strip,
+                None,
+                &payload,
+                drag.source,
+                Some(&monitor),
+                DropZone::Bottom
+            ));
+
+            // ...nor can it come from a controlled workspace
+            let controlled_workspace = LayoutController::controlled(
+                Memo::new(move || workspace_layout.cloned()),
+                Some(ignore),
+            );
+            assert!(!drop_payload(
+                strip,
+                None,
+                &payload,
+                Some(controlled_workspace),
+                Some(&monitor),
+                DropZone::Bottom
+            ));
+
+            // The tile is still in the workspace only
+            assert_eq!(tiles(workspace), ["sidebar", "editor", "terminal"]);
+            assert_eq!(tiles(strip), ["monitor"]);
+        });
+    }
+
+    #[test]
+    fn test_drop_on_divider() {
+        in_scope(|| {
+            let controller = LayoutController::signal(Signal::new(workspace()), None);
+            let root = controller.layout().read().root().cloned().unwrap();
+            let inner = parent_split(controller, "editor");
+            let terminal = DragPayload::Tile("terminal".to_string());
+            // The terminal is already next to the editor/terminal divider
+            assert!(!controller.can_drop_at_split(&terminal, &inner));
+            assert!(preview_drop_at_split(controller, None, &terminal, None, &root).is_some());
+            assert!(drop_payload_at_split(
+                controller,
+                None,
+                &terminal,
+                Some(controller),
+                &root
+            ));
+            // Tiles from another mosaic are only dropped on tiles and edges
+            let other = LayoutController::signal(Signal::new(workspace()), None);
+            let editor = DragPayload::Tile("editor".to_string());
+            assert!(preview_drop_at_split(controller, None, &editor, Some(other), &root).is_none());
+            assert!(!drop_payload_at_split(
+                controller,
+                None,
+                &editor,
+                Some(other),
+                &root
+            ));
+
+            assert_eq!(tiles(controller), ["sidebar", "terminal", "editor"]);
+            let layout = controller.layout();
+            let widths = ["sidebar", "terminal", "editor"].map(|tile_id| {
+                let (width, _) = layout.read().tile_size(&tile_id.to_string()).unwrap();
+                (width * 1000.0).round() / 1000.0
+            });
+            // The terminal takes a third, the sidebar keeps the 20% minimum of the split
+            assert_eq!(widths, [20.0, 33.333, 46.667]);
+        });
+    }
+
+    #[test]
+    fn test_splits_only_move_inside_their_layout() {
+        in_scope(|| {
+            let controller = LayoutController::signal(Signal::new(workspace()), None);
+            let inner = parent_split(controller, "editor");
+            let payload = DragPayload::Split(inner.clone());
+            let sidebar = "sidebar".to_string();
+            // Splits aren't inserted at dividers, nor dropped in other mosaics
+            assert!(!drop_payload_at_split(
+                controller,
+                None,
+                &payload,
+                Some(controller),
+                &inner
+            ));
+            assert!(!drop_payload(
+                controller,
+                None,
+                &payload,
+                None,
+                Some(&sidebar),
+                DropZone::Right
+            ));
+            assert!(drop_payload(
+                controller,
+                None,
+                &payload,
+                Some(controller),
+                Some(&sidebar),
+                DropZone::Right
+            ));
+            let layout = controller.layout();
+            let sizes = ["sidebar", "editor", "terminal"]
+                .map(|tile_id| layout.read().tile_size(&tile_id.to_string()));
+            assert_eq!(
+                sizes,
+                [Some((50.0, 100.0)), Some((50.0, 70.0)), Some((50.0, 30.0))]
+            );
+        });
+    }
+}
@@ -0,0 +1,365 @@
+use crate::types::{SplitDirection, TileId};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// Drop zone position when hovering over a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DropZone {
+    /// Top 25% of tile (creates vertical split with dragged tile on top)
+    Top,
+    /// Bottom 25% of tile (creates vertical split with dragged tile on bottom)
+    Bottom,
+    /// Left 25% of tile (creates horizontal split with dragged tile on left)
+    Left,
+    /// Right 25% of tile (creates horizontal split with dragged tile on right)
+    Right,
+    /// Center of tile, only with a `DropZoneGeometry` that has one (swaps the two tiles)
+    Center,
+}
+
+impl DropZone {
+    /// Get the split direction for this drop zone
+    ///
+    /// `Center` doesn't create a split, and reports `Horizontal`.
+    pub fn split_direction(&self) -> SplitDirection {
+        match self {
+            DropZone::Top | DropZone::Bottom => SplitDirection::Vertical,
+            DropZone::Left | DropZone::Right | DropZone::Center => SplitDirection::Horizontal,
+        }
+    }
+
+    /// Whether the dragged tile should be the first child in the split
+    pub fn dragged_is_first(&self) -> bool {
+        match self {
+            DropZone::Top | DropZone::Left => true,
+            DropZone::Bottom | DropZone::Right | DropZone::Center => false,
+        }
+    }
+}
+
+/// Decides where tiles may be dropped, see `MosaicLayout::set_drop_policy`
+///
+/// Called with the dragged tile, the target tile (`None` for the edges of the
+/// whole layout) and the drop zone.
+#[derive(Clone)]
+pub struct DropPolicy(Arc<PolicyFn>);
+
+type PolicyFn = dyn Fn(&TileId, Option<&TileId>, DropZone) -> bool + Send + Sync;
+
+impl DropPolicy {
+    pub fn new(
+        policy: impl Fn(&TileId, Option<&TileId>, DropZone) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(policy))
+    }
+
+    /// Whether `dragged` may be dropped in `zone` of `target`
+    pub fn allows(&self, dragged: &TileId, target: Option<&TileId>, zone: DropZone) -> bool {
+        (self.0)(dragged, target, zone)
+    }
+}
+
+/// Policies are only equal to themselves (or their clones)
+impl PartialEq for DropPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for DropPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DropPolicy")
+    }
+}
+
+/// How a tile is divided into drop zones
+///
+/// Either bands along each edge (the default, 30% wide), or four triangles
+/// meeting at the tile's diagonals like react-mosaic. Both can have a center
+/// zone, which swaps the dragged tile with the target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DropZoneGeometry {
+    /// Width of the edge bands, as a fraction of the tile's width or height
+    ///
+    /// Top and bottom bands span the whole width; left and right bands sit
+    /// between them. Not used by the diagonal geometry.
+    pub margin: f64,
+    /// Divide the tile along its diagonals instead of into edge bands
+    pub diagonal: bool,
+    /// Size of the center zone, as a fraction of the tile's width and height
+    /// (0 for none). With edge bands, it is cut to fit between them.
+    pub center: f64,
+}
+
+impl Default for DropZoneGeometry {
+    fn default() -> Self {
+        Self::edges(0.3)
+    }
+}
+
+/// Part of a tile covered by a drop zone, as fractions of the tile's size
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ZoneArea {
+    Rect {
+        left: f64,
+        top: f64,
+        right: f64,
+        bottom: f64,
+    },
+    Polygon([(f64, f64); 4]),
+}
+
+impl DropZoneGeometry {
+    /// Bands `margin` wide along each edge
+    pub fn edges(margin: f64) -> Self {
+        Self {
+            margin,
+            diagonal: false,
+            center: 0.0,
+        }
+    }
+
+    /// Four triangles meeting at the tile's diagonals, like react-mosaic
+    pub fn diagonal() -> Self {
+        Self {
+            diagonal: true,
+            ..Self::default()
+        }
+    }
+
+    /// Add a center zone of `size` (fraction of the tile's width and height)
+    pub fn with_center(mut self, size: f64) -> Self {
+        self.center = size;
+        self
+    }
+
+    /// Whether the geometry has a center zone
+    pub fn has_center(&self) -> bool {
+        self.center_bounds().is_some()
+    }
+
+    /// Drop zone at a position relative to the tile (`0.0..=1.0` on both axes)
+    ///
+    /// Returns None where there is no zone, e.g. the center of edge bands
+    /// without a center zone.
+    pub fn zone_at(&self, rel_x: f64, rel_y: f64) -> Option<DropZone> {
+        if let Some((start, end)) = self.center_bounds() {
+            if (start..=end).contains(&rel_x) && (start..=end).contains(&rel_y) {
+                return Some(DropZone::Center);
+            }
+        }
+
+        if self.diagonal {
+            let (dx, dy) = (rel_x - 0.5, rel_y - 0.5);
+            return Some(if dy.abs() >= dx.abs() {
+                if dy < 0.0 {
+                    DropZone::Top
+                } else {
+                    DropZone::Bottom
+                }
+            } else if dx < 0.0 {
+                DropZone::Left
+            } else {
+                DropZone::Right
+            });
+        }
+
+        // Check edge zones (priority: top and bottom in the corners)
+        if rel_y < self.margin {
+            Some(DropZone::Top)
+        } else if rel_y > 1.0 - self.margin {
+            Some(DropZone::Bottom)
+        } else if rel_x < self.margin {
+            Some(DropZone::Left)
+        } else if rel_x > 1.0 - self.margin {
+            Some(DropZone::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Drop zone under the cursor, given the client rect of the tile
+    pub fn zone_at_point(
+        &self,
+        mouse_x: f64,
+        mouse_y: f64,
+        rect_x: f64,
+        rect_y: f64,
+        rect_width: f64,
+        rect_height: f64,
+    ) -> Option<DropZone> {
+        // Calculate relative position (0.0 to 1.0)
+        let rel_x = ((mouse_x - rect_x) / rect_width).clamp(0.0, 1.0);
+        let rel_y = ((mouse_y - rect_y) / rect_height).clamp(0.0, 1.0);
+        self.zone_at(rel_x, rel_y)
+    }
+
+    /// CSS declarations placing an absolutely positioned overlay exactly on `zone`
+    ///
+    /// Triangles are drawn with `clip-path`. Returns `display: none;` for a
+    /// center zone the geometry doesn't have.
+    pub fn zone_css(&self, zone: DropZone) -> String {
+        let percent = |fraction: f64| fraction * 100.0;
+        match self.area(zone) {
+            Some(ZoneArea::Rect {
+                left,
+                top,
+                right,
+                bottom,
+            }) => format!(
+                "left: {}%; top: {}%; width: {}%; height: {}%;",
+                percent(left),
+                percent(top),
+                percent(right - left),
+                percent(bottom - top),
+            ),
+            Some(ZoneArea::Polygon(points)) => {
+                let points: Vec<String> = points
+                    .iter()
+                    .map(|(x, y)| format!("{}% {}%", percent(*x), percent(*y)))
+                    .collect();
+                format!(
+                    "left: 0; top: 0; width: 100%; height: 100%; clip-path: polygon({});",
+                    points.join(", ")
+                )
+            }
+            None => "display: none;".to_string(),
+        }
+    }
+
+    fn area(&self, zone: DropZone) -> Option<ZoneArea> {
+        let rect = |left, top, right, bottom| ZoneArea::Rect {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        if zone == DropZone::Center {
+            let (start, end) = self.center_bounds()?;
+            return Some(rect(start, start, end, end));
+        }
+
+        if self.diagonal {
+            // Trapezoids from each edge to the side of the center square
+            let (near, far) = self.center_bounds().unwrap_or((0.5, 0.5));
+            return Some(ZoneArea::Polygon(match zone {
+                DropZone::Top => [(0.0, 0.0), (1.0, 0.0), (far, near), (near, near)],
+                DropZone::Bottom => [(0.0, 1.0), (1.0, 1.0), (far, far), (near, far)],
+                DropZone::Left => [(0.0, 0.0), (0.0, 1.0), (near, far), (near, near)],
+                _ => [(1.0, 0.0), (1.0, 1.0), (far, far), (far, near)],
+            }));
+        }
+
+        let margin = self.margin;
+        Some(match zone {
+            DropZone::Top => rect(0.0, 0.0, 1.0, margin),
+            DropZone::Bottom => rect(0.0, 1.0 - margin, 1.0, 1.0),
+            DropZone::Left => rect(0.0, margin, margin, 1.0 - margin),
+            _ => rect(1.0 - margin, margin, 1.0, 1.0 - margin),
+        })
+    }
+
+    /// Start and end of the center zone, the same along both axes
+    fn center_bounds(&self) -> Option<(f64, f64)> {
+        if self.center <= 0.0 {
+            return None;
+        }
+        let (start, end) = (0.5 - self.center / 2.0, 0.5 + self.center / 2.0);
+        if self.diagonal {
+            return Some((start.max(0.0), end.min(1.0)));
+        }
+        let (start, end) = (start.max(self.margin), end.min(1.0 - self.margin));
+        (start < end).then_some((start, end))
+    }
+}
+
+/// Calculate which drop zone the cursor is in based on position within target element
+///
+/// Uses the default geometry (30% bands along each edge); see
+/// [`DropZoneGeometry::zone_at_point`] for the others.
+/// Returns None if in the center (no-drop zone)
+pub fn calculate_drop_zone(
+    mouse_x: f64,
+    mouse_y: f64,
+    rect_x: f64,
+    rect_y: f64,
+    rect_width: f64,
+    rect_height: f64,
+) -> Option<DropZone> {
+    DropZoneGeometry::default().zone_at_point(
+        mouse_x,
+        mouse_y,
+        rect_x,
+        rect_y,
+        rect_width,
+        rect_height,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_drop_zone_edges() {
+        let zone = |x, y| calculate_drop_zone(x, y, 0.0, 0.0, 100.0, 100.0);
+        assert_eq!(zone(50.0, 10.0), Some(DropZone::Top));
+        assert_eq!(zone(50.0, 90.0), Some(DropZone::Bottom));
+        assert_eq!(zone(10.0, 50.0), Some(DropZone::Left));
+        assert_eq!(zone(90.0, 50.0), Some(DropZone::Right));
+        assert_eq!(zone(50.0, 50.0), None);
+    }
+
+    #[test]
+    fn test_drop_zone_split_mapping() {
+        assert_eq!(DropZone::Top.split_direction(), SplitDirection::Vertical);
+        assert_eq!(DropZone::Left.split_direction(), SplitDirection::Horizontal);
+        assert!(DropZone::Left.dragged_is_first());
+        assert!(!DropZone::Bottom.dragged_is_first());
+    }
+
+    #[test]
+    fn test_diagonal_and_center_zones() {
+        let diagonal = DropZoneGeometry::diagonal();
+        // Corners are split along the diagonals instead of going to top/bottom
+        assert_eq!(diagonal.zone_at(0.05, 0.2), Some(DropZone::Left));
+        assert_eq!(diagonal.zone_at(0.2, 0.05), Some(DropZone::Top));
+        assert_eq!(diagonal.zone_at(0.9, 0.8), Some(DropZone::Right));
+
+        let centered = diagonal.with_center(0.4);
+        assert_eq!(centered.zone_at(0.5, 0.5), Some(DropZone::Center));
+        assert_eq!(centered.zone_at(0.5, 0.25), Some(DropZone::Top));
+
+        // With edge bands, the center zone fits between them
+        let edges = DropZoneGeometry::edges(0.25).with_center(0.8);
+        assert_eq!(edges.zone_at(0.2, 0.5), Some(DropZone::Left));
+        assert_eq!(edges.zone_at(0.3, 0.7), Some(DropZone::Center));
+        assert!(!DropZoneGeometry::edges(0.5).with_center(0.4).has_center());
+        assert_eq!(DropZoneGeometry::default().zone_at(0.5, 0.5), None);
+    }
+
+    #[test]
+    fn test_zone_css_matches_geometry() {
+        let edges = DropZoneGeometry::edges(0.25);
+        assert_eq!(
+            edges.zone_css(DropZone::Top),
+            "left: 0%; top: 0%; width: 100%; height: 25%;"
+        );
+        assert_eq!(
+            edges.zone_css(DropZone::Left),
+            "left: 0%; top: 25%; width: 25%; height: 50%;"
+        );
+        assert_eq!(edges.zone_css(DropZone::Center), "display: none;");
+
+        let diagonal = DropZoneGeometry::diagonal().with_center(0.5);
+        assert_eq!(
+            diagonal.zone_css(DropZone::Top),
+            "left: 0; top: 0; width: 100%; height: 100%; clip-path: polygon(0% 0%, 100% 0%, 75% 25%, 25% 25%);"
+        );
+        assert_eq!(
+            diagonal.zone_css(DropZone::Center),
+            "left: 25%; top: 25%; width: 50%; height: 50%;"
+        );
+    }
+}
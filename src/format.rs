@@ -0,0 +1,285 @@
+use crate::layout::MosaicLayout;
+use crate::storage::StorageError;
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Current version of the serialized layout format
+///
+/// Bump this whenever the shape of `MosaicLayout` or `Node` changes, and add
+/// the matching step to [`MIGRATIONS`].
+pub const LAYOUT_FORMAT_VERSION: u32 = 2;
+
+/// A single migration step, upgrading a layout payload by exactly one version
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// Migration steps, indexed by the version they upgrade *from*
+///
+/// `MIGRATIONS[0]` upgrades a version 0 payload to version 1, and so on.
+/// The length of this slice must always equal `LAYOUT_FORMAT_VERSION`.
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1, migrate_v1_to_v2];
+
+/// Reasons a serialized layout could not be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadError {
+    /// No layout is stored under the requested key
+    NotFound,
+
+    /// The storage backend failed to read the payload
+    Storage(StorageError),
+
+    /// The payload is not valid JSON or is not a layout
+    Malformed(String),
+
+    /// The payload was written by a newer version of the library
+    UnsupportedVersion {
+        /// Version found in the payload
+        found: u32,
+        /// Newest version this build can read
+        supported: u32,
+    },
+
+    /// A migration step failed to upgrade the payload
+    MigrationFailed {
+        /// Version the failing step was upgrading from
+        from: u32,
+        /// Description of the failure
+        message: String,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::NotFound => write!(f, "no saved layout found"),
+            LoadError::Storage(err) => write!(f, "{}", err),
+            LoadError::Malformed(msg) => write!(f, "malformed layout payload: {}", msg),
+            LoadError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "layout format version {} is newer than the supported version {}",
+                found, supported
+            ),
+            LoadError::MigrationFailed { from, message } => write!(
+                f,
+                "failed to migrate layout from version {}: {}",
+                from, message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<StorageError> for LoadError {
+    fn from(err: StorageError) -> Self {
+        LoadError::Storage(err)
+    }
+}
+
+/// Wrap a layout into a versioned payload
+pub(crate) fn encode(layout: &MosaicLayout) -> Result<String, String> {
+    let body = serde_json::to_value(layout).map_err(|e| e.to_string())?;
+    let envelope = json!({
+        "version": LAYOUT_FORMAT_VERSION,
+        "layout": body,
+    });
+    serde_json::to_string(&envelope).map_err(|e| e.to_string())
+}
+
+/// Parse a payload of any known version, migrating it up to the current one
+pub(crate) fn decode(json: &str) -> Result<MosaicLayout, LoadError> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| LoadError::Malformed(e.to_string()))?;
+    let (mut version, mut body) = split_envelope(value)?;
+
+    if version > LAYOUT_FORMAT_VERSION {
+        return Err(LoadError::UnsupportedVersion {
+            found: version,
+            supported: LAYOUT_FORMAT_VERSION,
+        });
+    }
+
+    while version < LAYOUT_FORMAT_VERSION {
+        let step = MIGRATIONS[version as usize];
+        body = step(body).map_err(|message| LoadError::MigrationFailed {
+            from: version,
+            message,
+        })?;
+        version += 1;
+    }
+
+    serde_json::from_value(body).map_err(|e| LoadError::Malformed(e.to_string()))
+}
+
+/// Split a payload into its format version and layout body
+///
+/// Payloads written before versioning was introduced (0.1 and 0.2) are the bare
+/// layout object and are treated as version 0.
+fn split_envelope(value: Value) -> Result<(u32, Value), LoadError> {
+    let Value::Object(mut map) = value else {
+        return Err(LoadError::Malformed("expected a JSON object".to_string()));
+    };
+
+    match map.remove("version") {
+        None => Ok((0, Value::Object(map))),
+        Some(version) => {
+            let version = version
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| LoadError::Malformed("invalid format version".to_string()))?;
+            let body = map
+                .remove("layout")
+                .ok_or_else(|| LoadError::Malformed("missing layout body".to_string()))?;
+            Ok((version, body))
+        }
+    }
+}
+
+/// Version 0 -> 1
+///
+/// Version 1 only introduced the versioned envelope, the layout body is unchanged.
+fn migrate_v0_to_v1(body: Value) -> Result<Value, String> {
+    if !body.is_object() {
+        return Err("expected a layout object".to_string());
+    }
+    Ok(body)
+}
+
+/// Version 1 -> 2
+///
+/// Tiles replaced their `locked` flag with a set of capabilities; a locked tile
+/// can't be closed nor be dropped on, like before.
+fn migrate_v1_to_v2(mut body: Value) -> Result<Value, String> {
+    let nodes = body
+        .get_mut("nodes")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| "expected a nodes object".to_string())?;
+
+    for node in nodes.values_mut() {
+        let Some(tile) = node.get_mut("Tile").and_then(Value::as_object_mut) else {
+            continue;
+        };
+        let locked = match tile.remove("locked") {
+            Some(locked) => locked
+                .as_bool()
+                .ok_or_else(|| "invalid locked flag".to_string())?,
+            None => false,
+        };
+        tile.insert(
+            "capabilities".to_string(),
+            json!({
+                "closable": !locked,
+                "draggable": true,
+                "drop_target": !locked,
+                "resizable": true,
+                "splittable": true,
+            }),
+        );
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::TileCapabilities;
+    use crate::types::SplitDirection;
+
+    /// A layout as saved by 0.1 / 0.2, before versioning existed
+    const LEGACY_PAYLOAD: &str = r#"{
+        "nodes": {
+            "node_0": {"Tile": {"id": "node_0", "tile_id": "a", "parent": "node_2", "locked": false}},
+            "node_1": {"Tile": {"id": "node_1", "tile_id": "b", "parent": "node_2", "locked": false}},
+            "node_2": {"Split": {
+                "id": "node_2", "direction": "Horizontal", "first": "node_0", "second": "node_1",
+                "split_percentage": 30.0, "parent": null, "locked": false,
+                "min_percentage": 20.0, "max_percentage": 80.0
+            }}
+        },
+        "root": "node_2",
+        "next_id": 3
+    }"#;
+
+    #[test]
+    fn test_roundtrip_current_version() {
+        let mut layout = MosaicLayout::new("a".to_string());
+        layout.split_tile(
+            &"a".to_string(),
+            SplitDirection::Vertical,
+            "b".to_string(),
+            40.0,
+        );
+
+        let json = layout.to_json().unwrap();
+        assert!(json.contains(&format!("\"version\":{}", LAYOUT_FORMAT_VERSION)));
+
+        let restored = MosaicLayout::from_json(&json).unwrap();
+        assert_eq!(restored.get_all_tiles(), layout.get_all_tiles());
+    }
+
+    #[test]
+    fn test_legacy_payload_is_migrated() {
+        let layout = MosaicLayout::from_json(LEGACY_PAYLOAD).unwrap();
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_locked_flag_becomes_capabilities() {
+        let json = format!(
+            r#"{{"version": 1, "layout": {}}}"#,
+            LEGACY_PAYLOAD.replacen(
+                r#""tile_id": "a", "parent": "node_2", "locked": false"#,
+                r#""tile_id": "a", "parent": "node_2", "locked": true"#,
+                1
+            )
+        );
+        let layout = MosaicLayout::from_json(&json).unwrap();
+        assert_eq!(
+            layout.tile_capabilities(&"a".to_string()),
+            Some(TileCapabilities::locked())
+        );
+        assert_eq!(
+            layout.tile_capabilities(&"b".to_string()),
+            Some(TileCapabilities::default())
+        );
+        assert!(!layout.clone().close_tile(&"a".to_string()));
+
+        let json = layout.to_json().unwrap();
+        assert!(json.contains(r#""capabilities":{"closable":false"#));
+        assert!(!json.contains("\"locked\":true"));
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let json = format!(
+            r#"{{"version": {}, "layout": {{}}}}"#,
+            LAYOUT_FORMAT_VERSION + 1
+        );
+        assert_eq!(
+            MosaicLayout::from_json(&json).unwrap_err(),
+            LoadError::UnsupportedVersion {
+                found: LAYOUT_FORMAT_VERSION + 1,
+                supported: LAYOUT_FORMAT_VERSION,
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_payload_reports_error() {
+        assert!(matches!(
+            MosaicLayout::from_json("not json"),
+            Err(LoadError::Malformed(_))
+        ));
+        assert!(matches!(
+            MosaicLayout::from_json(r#"{"version": 1}"#),
+            Err(LoadError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_migrations_cover_every_version() {
+        assert_eq!(MIGRATIONS.len(), LAYOUT_FORMAT_VERSION as usize);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::layout::MosaicLayout;
+use crate::node::{Node, TileCapabilities};
+use crate::types::{NodeId, SplitDirection, TileId};
+
+/// A length along one axis of the mosaic
+///
+/// `percent` is a share of the mosaic's size, and `tracks` a number of divider
+/// tracks (a divider and the gap on each side of it): a split gives its first
+/// child `split_percentage` of its size, and the second child what remains
+/// after the divider track.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Length {
+    /// Percentage of the mosaic's width or height
+    pub percent: f64,
+    /// Number of divider tracks added to (or removed from) the percentage
+    pub tracks: f64,
+}
+
+impl Length {
+    /// The whole width or height of the mosaic
+    pub const FULL: Length = Length {
+        percent: 100.0,
+        tracks: 0.0,
+    };
+
+    /// `percentage`% of this length
+    fn share(self, percentage: f64) -> Length {
+        Length {
+            percent: self.percent * percentage / 100.0,
+            tracks: self.tracks * percentage / 100.0,
+        }
+    }
+
+    fn plus(self, other: Length) -> Length {
+        Length {
+            percent: self.percent + other.percent,
+            tracks: self.tracks + other.tracks,
+        }
+    }
+
+    fn minus(self, other: Length) -> Length {
+        Length {
+            percent: self.percent - other.percent,
+            tracks: self.tracks - other.tracks,
+        }
+    }
+
+    /// One divider track
+    fn track() -> Length {
+        Length {
+            percent: 0.0,
+            tracks: 1.0,
+        }
+    }
+
+    /// CSS value of the length, given the CSS size of a divider track
+    pub fn to_css(&self, track: &str) -> String {
+        if self.tracks == 0.0 {
+            format!("{}%", self.percent)
+        } else if self.tracks < 0.0 {
+            format!("calc({}% - {} * {})", self.percent, -self.tracks, track)
+        } else {
+            format!("calc({}% + {} * {})", self.percent, self.tracks, track)
+        }
+    }
+}
+
+/// Position and size of an area of the mosaic
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    /// Distance from the left edge of the mosaic
+    pub left: Length,
+    /// Distance from the top edge of the mosaic
+    pub top: Length,
+    pub width: Length,
+    pub height: Length,
+}
+
+impl Rect {
+    /// The whole mosaic
+    pub const FULL: Rect = Rect {
+        left: Length {
+            percent: 0.0,
+            tracks: 0.0,
+        },
+        top: Length {
+            percent: 0.0,
+            tracks: 0.0,
+        },
+        width: Length::FULL,
+        height: Length::FULL,
+    };
+
+    /// Areas of the two children of a split covering this rect
+    fn split(&self, direction: SplitDirection, percentage: f64) -> (Rect, Rect) {
+        match direction {
+            SplitDirection::Horizontal => {
+                let first = self.width.share(percentage);
+                let second = self.width.minus(first).minus(Length::track());
+                (
+                    Rect {
+                        width: first,
+                        ..*self
+                    },
+                    Rect {
+                        left: self.left.plus(first).plus(Length::track()),
+                        width: second,
+                        ..*self
+                    },
+                )
+            }
+            SplitDirection::Vertical => {
+                let first = self.height.share(percentage);
+                let second = self.height.minus(first).minus(Length::track());
+                (
+                    Rect {
+                        height: first,
+                        ..*self
+                    },
+                    Rect {
+                        top: self.top.plus(first).plus(Length::track()),
+                        height: second,
+                        ..*self
+                    },
+                )
+            }
+        }
+    }
+
+    /// CSS declarations placing an absolutely positioned element on this rect
+    pub fn to_css(&self, track: &str) -> String {
+        format!(
+            "left: {}; top: {}; width: {}; height: {};",
+            self.left.to_css(track),
+            self.top.to_css(track),
+            self.width.to_css(track),
+            self.height.to_css(track),
+        )
+    }
+}
+
+/// Area taken by a tile
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileGeometry {
+    pub tile_id: TileId,
+    /// ID of the tile's node in the layout
+    pub node_id: NodeId,
+    /// What users can do with the tile
+    pub capabilities: TileCapabilities,
+    pub rect: Rect,
+}
+
+/// Area covered by a split, both children and the divider track between them
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitGeometry {
+    pub node_id: NodeId,
+    pub direction: SplitDirection,
+    /// Share of the split given to its first child (percentage)
+    pub split_percentage: f64,
+    pub rect: Rect,
+}
+
+/// Flat description of where every tile and split of a layout goes
+///
+/// Lets a renderer position tiles directly from the layout instead of nesting
+/// them in their splits, so moving a tile doesn't change where it lives in the
+/// rendered tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayoutGeometry {
+    /// Tiles in tree order
+    pub tiles: Vec<TileGeometry>,
+    /// Splits, parents before their children
+    pub splits: Vec<SplitGeometry>,
+}
+
+impl MosaicLayout {
+    /// Geometry of the whole layout
+    pub fn geometry(&self) -> LayoutGeometry {
+        match self.root() {
+            Some(root) => self.geometry_of(root),
+            None => LayoutGeometry::default(),
+        }
+    }
+
+    /// Geometry of a subtree laid out over the whole mosaic (e.g. a maximized tile)
+    pub fn geometry_of(&self, node_id: &NodeId) -> LayoutGeometry {
+        let mut geometry = LayoutGeometry::default();
+        self.collect_geometry(node_id, Rect::FULL, &mut geometry);
+        geometry
+    }
+
+    fn collect_geometry(&self, node_id: &NodeId, rect: Rect, geometry: &mut LayoutGeometry) {
+        match self.get_node(node_id) {
+            Some(Node::Tile {
+                id,
+                tile_id,
+                capabilities,
+                ..
+            }) => geometry.tiles.push(TileGeometry {
+                tile_id: tile_id.clone(),
+                node_id: id.clone(),
+                capabilities: *capabilities,
+                rect,
+            }),
+            Some(Node::Split {
+                id,
+                direction,
+                first,
+                second,
+                split_percentage,
+                ..
+            }) => {
+                geometry.splits.push(SplitGeometry {
+                    node_id: id.clone(),
+                    direction: *direction,
+                    split_percentage: *split_percentage,
+                    rect,
+                });
+                let (first_rect, second_rect) = rect.split(*direction, *split_percentage);
+                self.collect_geometry(first, first_rect, geometry);
+                self.collect_geometry(second, second_rect, geometry);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{tile, MosaicBuilder};
+
+    fn length(percent: f64, tracks: f64) -> Length {
+        Length { percent, tracks }
+    }
+
+    #[test]
+    fn test_geometry_of_nested_splits() {
+        // sidebar | (editor / terminal)
+        let layout = MosaicBuilder::horizontal()
+            .left(tile("sidebar"))
+            .right(
+                MosaicBuilder::vertical()
+                    .top(tile("editor"))
+                    .bottom(tile("terminal"))
+                    .split(70.0)
+                    .build_tree(),
+            )
+            .split(25.0)
+            .build();
+        let geometry = layout.geometry();
+
+        assert_eq!(geometry.splits.len(), 2);
+        let tiles: Vec<_> = geometry.tiles.iter().map(|t| t.tile_id.as_str()).collect();
+        assert_eq!(tiles, ["sidebar", "editor", "terminal"]);
+
+        let sidebar = geometry.tiles[0].rect;
+        assert_eq!(sidebar.width, length(25.0, 0.0));
+        assert_eq!(sidebar.height, Length::FULL);
+
+        // The right side starts after the sidebar and the divider track
+        let editor = geometry.tiles[1].rect;
+        assert_eq!(editor.left, length(25.0, 1.0));
+        assert_eq!(editor.width, length(75.0, -1.0));
+        assert_eq!(editor.height, length(70.0, 0.0));
+
+        let terminal = geometry.tiles[2].rect;
+        assert_eq!(terminal.top, length(70.0, 1.0));
+        assert_eq!(terminal.height, length(30.0, -1.0));
+    }
+
+    #[test]
+    fn test_geometry_of_maximized_tile() {
+        let mut layout = MosaicLayout::new("a".to_string());
+        layout.split_tile(
+            &"a".to_string(),
+            SplitDirection::Horizontal,
+            "b".to_string(),
+            50.0,
+        );
+        let node_id = layout.find_tile(&"b".to_string()).unwrap();
+
+        let geometry = layout.geometry_of(&node_id);
+        assert!(geometry.splits.is_empty());
+        assert_eq!(geometry.tiles.len(), 1);
+        assert_eq!(geometry.tiles[0].rect, Rect::FULL);
+        assert!(MosaicLayout::empty().geometry().tiles.is_empty());
+    }
+
+    #[test]
+    fn test_length_to_css() {
+        assert_eq!(length(25.0, 0.0).to_css("8px"), "25%");
+        assert_eq!(length(25.0, 1.0).to_css("8px"), "calc(25% + 1 * 8px)");
+        assert_eq!(length(75.0, -0.5).to_css("8px"), "calc(75% - 0.5 * 8px)");
+    }
+}
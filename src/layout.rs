@@ -1,13 +1,25 @@
-use crate::node::Node;
+use crate::drop_zone::{DropPolicy, DropZone};
+use crate::format::LoadError;
+use crate::node::{Node, TileCapabilities};
+#[cfg(feature = "web")]
+use crate::storage::LocalStorage;
+use crate::storage::{LayoutStorage, StorageError};
 use crate::types::{NodeId, SplitDirection, TileId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Share of the layout given to a tile docked with `dock_at_root` (percentage)
+pub const DOCK_PERCENTAGE: f64 = 30.0;
+
+/// Share of a split given to a tile inserted at its boundary with `insert_at_split`
+/// (percentage); the two panes shrink proportionally to make room
+pub const SPLIT_INSERT_PERCENTAGE: f64 = 100.0 / 3.0;
+
 /// Main layout structure using HashMap for O(1) operations
 ///
 /// This is the core data structure that manages the mosaic layout.
 /// Internally uses a HashMap for fast lookups, but provides a tree-like API for ease of use.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MosaicLayout {
     /// All nodes indexed by ID - O(1) access
     nodes: HashMap<NodeId, Node>,
@@ -17,6 +29,10 @@ pub struct MosaicLayout {
 
     /// Counter for generating unique node IDs
     next_id: usize,
+
+    /// Where tiles may be dropped, on top of their capabilities (not serialized)
+    #[serde(skip)]
+    drop_policy: Option<DropPolicy>,
 }
 
 impl MosaicLayout {
@@ -29,7 +45,7 @@ impl MosaicLayout {
             id: root_id.clone(),
             tile_id,
             parent: None,
-            locked: false,
+            capabilities: TileCapabilities::default(),
         };
 
         nodes.insert(root_id.clone(), root_node);
@@ -38,6 +54,7 @@ impl MosaicLayout {
             nodes,
             root: Some(root_id),
             next_id: 1,
+            drop_policy: None,
         }
     }
 
@@ -47,6 +64,7 @@ impl MosaicLayout {
             nodes: HashMap::new(),
             root: None,
             next_id: 0,
+            drop_policy: None,
         }
     }
 
@@ -107,21 +125,21 @@ impl MosaicLayout {
         })
     }
 
-    /// Update split percentage - O(1)
+    /// Update split percentage - O(depth)
     ///
-    /// Clamps the percentage to the node's min/max values
+    /// Clamps the percentage to the node's min/max values. Returns false if
+    /// `can_resize_split` refuses it.
     pub fn update_split(&mut self, node_id: &NodeId, percentage: f64) -> bool {
+        if !self.can_resize_split(node_id) {
+            return false;
+        }
         if let Some(Node::Split {
             split_percentage,
             min_percentage,
             max_percentage,
-            locked,
             ..
         }) = self.nodes.get_mut(node_id)
         {
-            if *locked {
-                return false;
-            }
             *split_percentage = percentage.clamp(*min_percentage, *max_percentage);
             true
         } else {
@@ -131,7 +149,8 @@ impl MosaicLayout {
 
     /// Split a tile into two panes - O(1)
     ///
-    /// Replaces the tile node with a split node containing the original tile and a new tile
+    /// Replaces the tile node with a split node containing the original tile and a new tile.
+    /// Returns false if the tile wasn't found or isn't `splittable`.
     pub fn split_tile(
         &mut self,
         tile_id: &TileId,
@@ -144,6 +163,12 @@ impl MosaicLayout {
             Some(id) => id,
             None => return false,
         };
+        if !self
+            .capabilities_of(&tile_node_id)
+            .is_some_and(|capabilities| capabilities.splittable)
+        {
+            return false;
+        }
 
         // Get the parent ID before we modify anything
         let parent_id = self
@@ -157,7 +182,7 @@ impl MosaicLayout {
             id: new_tile_node_id.clone(),
             tile_id: new_tile_id,
             parent: None, // Will be set below
-            locked: false,
+            capabilities: TileCapabilities::default(),
         };
 
         // Create split node
@@ -210,8 +235,11 @@ impl MosaicLayout {
             None => return false,
         };
 
-        // Check if locked
-        if let Some(Node::Tile { locked: true, .. }) = self.nodes.get(&tile_node_id) {
+        // Check if it can be closed
+        if !self
+            .capabilities_of(&tile_node_id)
+            .is_some_and(|capabilities| capabilities.closable)
+        {
             return false;
         }
 
@@ -276,6 +304,7 @@ impl MosaicLayout {
     ///
     /// This is used for drag-and-drop operations. It removes the dragged tile from its
     /// current position and inserts it by splitting the target tile in the specified direction.
+    /// `DropZone::Center` swaps the two tiles instead.
     ///
     /// Returns true if the operation succeeded, false if either tile wasn't found or
     /// if trying to drop a tile onto itself.
@@ -283,7 +312,7 @@ impl MosaicLayout {
         &mut self,
         dragged_tile_id: &TileId,
         target_tile_id: &TileId,
-        drop_zone: crate::drag_drop::DropZone,
+        drop_zone: DropZone,
     ) -> bool {
         // Don't allow dropping on itself
         if dragged_tile_id == target_tile_id {
@@ -301,128 +330,751 @@ impl MosaicLayout {
             None => return false,
         };
 
-        // Check if target is locked
-        if let Some(Node::Tile { locked: true, .. }) = self.nodes.get(&target_node_id) {
+        if !self.can_drop(dragged_tile_id, Some(target_tile_id), drop_zone) {
             return false;
         }
 
+        // The center zone swaps the two tiles instead of splitting the target
+        if drop_zone == DropZone::Center {
+            return self.swap_tiles(dragged_tile_id, target_tile_id);
+        }
+
         // Step 1: Remove dragged tile from its current position (but keep the node)
-        // We need to preserve the dragged tile node and its data
-        let dragged_tile_node = match self.nodes.get(&dragged_node_id).cloned() {
-            Some(Node::Tile { tile_id, locked, .. }) => Node::Tile {
-                id: dragged_node_id.clone(),
-                tile_id,
-                parent: None, // Will be updated below
-                locked,
-            },
+        if !self.detach_node(&dragged_node_id) {
+            return false;
+        }
+
+        // Step 2: Split the target tile and insert the dragged tile (default 50/50 split)
+        self.attach_beside(&dragged_node_id, &target_node_id, drop_zone, 50.0);
+
+        true
+    }
+
+    /// Swap the positions of two tiles, each keeping its metadata (e.g. its capabilities)
+    ///
+    /// Returns false if either tile wasn't found, or both are the same.
+    pub fn swap_tiles(&mut self, first_tile_id: &TileId, second_tile_id: &TileId) -> bool {
+        let (first_id, second_id) = match (
+            self.find_tile(first_tile_id),
+            self.find_tile(second_tile_id),
+        ) {
+            (Some(first), Some(second)) if first != second => (first, second),
             _ => return false,
         };
+        let (mut first, mut second) =
+            match (self.nodes.remove(&first_id), self.nodes.remove(&second_id)) {
+                (Some(first), Some(second)) => (first, second),
+                _ => return false,
+            };
+
+        // Each node takes the other's place in the tree
+        let (first_parent, second_parent) = (first.parent().cloned(), second.parent().cloned());
+        for (node, id) in [(&mut first, &second_id), (&mut second, &first_id)] {
+            if let Node::Tile { id: node_id, .. } = node {
+                *node_id = id.clone();
+            }
+        }
+        first.set_parent(second_parent);
+        second.set_parent(first_parent);
+        self.nodes.insert(second_id, first);
+        self.nodes.insert(first_id, second);
+        true
+    }
+
+    /// Insert a tile that isn't in the layout yet by splitting a target tile
+    ///
+    /// Used when an external item (e.g. an entry of a panel palette) is dropped on
+    /// a tile. Returns false if `new_tile_id` is already in the layout, or if the
+    /// target wasn't found or can't be dropped on.
+    pub fn insert_new_tile(
+        &mut self,
+        new_tile_id: TileId,
+        target_tile_id: &TileId,
+        drop_zone: DropZone,
+    ) -> bool {
+        if self.find_tile(&new_tile_id).is_some() {
+            return false;
+        }
+        let target_node_id = match self.find_tile(target_tile_id) {
+            Some(id) => id,
+            None => return false,
+        };
+        if !self.can_drop(&new_tile_id, Some(target_tile_id), drop_zone) {
+            return false;
+        }
 
-        // Get parent of dragged tile (before removing it)
-        let dragged_parent_id = self
-            .nodes
-            .get(&dragged_node_id)
-            .and_then(|n| n.parent().cloned());
+        let node_id = self.add_detached_tile(new_tile_id);
+        self.attach_beside(&node_id, &target_node_id, drop_zone, 50.0);
+        true
+    }
 
-        // If dragged tile has a parent (it's in a split), remove it properly
-        if let Some(parent_id) = dragged_parent_id {
-            // Get sibling node ID
-            let sibling_id = match self.nodes.get(&parent_id) {
-                Some(Node::Split { first, second, .. }) => {
-                    if first == &dragged_node_id {
-                        second.clone()
-                    } else {
-                        first.clone()
-                    }
-                }
-                _ => return false,
+    /// Get all tile IDs in the layout (in traversal order)
+    pub fn get_all_tiles(&self) -> Vec<TileId> {
+        let mut tiles = Vec::new();
+        if let Some(root_id) = &self.root {
+            self.collect_tiles(root_id, &mut tiles);
+        }
+        tiles
+    }
+
+    /// Dock a tile along an edge of the whole layout
+    ///
+    /// Removes the tile from its current position and wraps the remaining root
+    /// in a new split, with the tile taking `DOCK_PERCENTAGE` of the space on the
+    /// side given by `drop_zone` (e.g. a full-width terminal with `DropZone::Bottom`).
+    ///
+    /// Returns false if the tile wasn't found or is the only tile of the layout.
+    pub fn dock_at_root(&mut self, tile_id: &TileId, drop_zone: DropZone) -> bool {
+        let node_id = match self.find_tile(tile_id) {
+            Some(id) => id,
+            None => return false,
+        };
+        if self.root.as_ref() == Some(&node_id)
+            || !self.can_drop(tile_id, None, drop_zone)
+            || !self.detach_node(&node_id)
+        {
+            return false;
+        }
+        let root_id = match self.root.clone() {
+            Some(id) => id,
+            None => return false,
+        };
+
+        self.attach_beside(&node_id, &root_id, drop_zone, DOCK_PERCENTAGE);
+        true
+    }
+
+    /// Dock a tile that isn't in the layout yet along an edge of the whole layout
+    ///
+    /// An empty layout simply gets the tile as its root. Returns false if
+    /// `new_tile_id` is already in the layout.
+    pub fn dock_new_tile_at_root(&mut self, new_tile_id: TileId, drop_zone: DropZone) -> bool {
+        if self.find_tile(&new_tile_id).is_some() || !self.can_drop(&new_tile_id, None, drop_zone) {
+            return false;
+        }
+
+        let node_id = self.add_detached_tile(new_tile_id);
+        match self.root.clone() {
+            Some(root_id) => self.attach_beside(&node_id, &root_id, drop_zone, DOCK_PERCENTAGE),
+            None => self.root = Some(node_id),
+        }
+        true
+    }
+
+    /// Move a tile from one layout to another, keeping its metadata (e.g. its capabilities)
+    ///
+    /// The tile is inserted next to `target` in `to` (50/50 split), or docked
+    /// along the edge of `to` given by `drop_zone` when there is no target, as
+    /// with `dock_new_tile_at_root`.
+    ///
+    /// Returns false and leaves both layouts untouched if the tile isn't in
+    /// `from`, is already in `to`, or if the target wasn't found or can't be dropped on.
+    pub fn transfer_tile(
+        from: &mut MosaicLayout,
+        to: &mut MosaicLayout,
+        tile_id: &TileId,
+        target: Option<&TileId>,
+        drop_zone: DropZone,
+    ) -> bool {
+        if to.find_tile(tile_id).is_some() || !to.can_drop(tile_id, target, drop_zone) {
+            return false;
+        }
+        let target_node_id = match target {
+            Some(target) => match to.find_tile(target) {
+                Some(id) => Some(id),
+                None => return false,
+            },
+            None => None,
+        };
+        let node_id = match from.find_tile(tile_id) {
+            Some(id) => id,
+            None => return false,
+        };
+        if !from
+            .capabilities_of(&node_id)
+            .is_some_and(|capabilities| capabilities.draggable)
+        {
+            return false;
+        }
+
+        // Take the node out of `from` and give it an ID of `to`
+        if !from.detach_node(&node_id) {
+            return false;
+        }
+        let mut node = match from.nodes.remove(&node_id) {
+            Some(node) => node,
+            None => return false,
+        };
+        let new_node_id = to.gen_id();
+        if let Node::Tile { id, .. } = &mut node {
+            *id = new_node_id.clone();
+        }
+        to.nodes.insert(new_node_id.clone(), node);
+
+        match (target_node_id, to.root.clone()) {
+            (Some(target_id), _) => to.attach_beside(&new_node_id, &target_id, drop_zone, 50.0),
+            (None, Some(root_id)) => {
+                to.attach_beside(&new_node_id, &root_id, drop_zone, DOCK_PERCENTAGE)
+            }
+            (None, None) => to.root = Some(new_node_id),
+        }
+        true
+    }
+
+    /// Move any node, a single tile or a whole split with everything in it
+    ///
+    /// The node is inserted next to `target` (50/50 split), or docked along the
+    /// edge of the layout given by `drop_zone` when there is no target, as with
+    /// `insert_tile_with_split` and `dock_at_root`. A moved split keeps its
+    /// inner arrangement (e.g. an editor over a terminal moves as a group).
+    ///
+    /// Returns false if `can_move_node` refuses the move.
+    pub fn move_node(
+        &mut self,
+        node_id: &NodeId,
+        target: Option<&TileId>,
+        drop_zone: DropZone,
+    ) -> bool {
+        if !self.can_move_node(node_id, target, drop_zone) {
+            return false;
+        }
+        if let Some(Node::Tile { tile_id, .. }) = self.nodes.get(node_id) {
+            let tile_id = tile_id.clone();
+            return match target {
+                Some(target) => self.insert_tile_with_split(&tile_id, target, drop_zone),
+                None => self.dock_at_root(&tile_id, drop_zone),
             };
+        }
 
-            // Get grandparent ID
-            let grandparent_id = self.nodes.get(&parent_id).and_then(|n| n.parent().cloned());
+        let target_node_id = match target.map(|target| self.find_tile(target)) {
+            Some(Some(id)) => Some(id),
+            Some(None) => return false,
+            None => None,
+        };
+        if !self.detach_node(node_id) {
+            return false;
+        }
+        match (target_node_id, self.root.clone()) {
+            (Some(target_id), _) => self.attach_beside(node_id, &target_id, drop_zone, 50.0),
+            (None, Some(root_id)) => {
+                self.attach_beside(node_id, &root_id, drop_zone, DOCK_PERCENTAGE)
+            }
+            (None, None) => self.root = Some(node_id.clone()),
+        }
+        true
+    }
 
-            // Update sibling's parent to grandparent
-            if let Some(sibling_node) = self.nodes.get_mut(&sibling_id) {
-                sibling_node.set_parent(grandparent_id.clone());
+    /// Whether `move_node` may move `node_id` in `zone` of `target`, or along the
+    /// edge of the layout given by `zone` when `target` is `None`
+    ///
+    /// A tile follows `can_drop`. A split can't be the root, dropped on one of
+    /// its own tiles or in `DropZone::Center`, and every tile in it must be
+    /// allowed by `can_drop`.
+    pub fn can_move_node(&self, node_id: &NodeId, target: Option<&TileId>, zone: DropZone) -> bool {
+        match self.nodes.get(node_id) {
+            Some(Node::Tile { tile_id, .. }) => return self.can_drop(tile_id, target, zone),
+            Some(Node::Split { .. }) => {}
+            None => return false,
+        }
+        if zone == DropZone::Center || self.root.as_ref() == Some(node_id) {
+            return false;
+        }
+        // A subtree can't be dropped into itself
+        if let Some(target) = target {
+            match self.find_tile(target) {
+                Some(target_id) if !self.contains_node(node_id, &target_id) => {}
+                _ => return false,
             }
+        }
+        self.subtree_tiles(node_id)
+            .iter()
+            .all(|tile_id| self.can_drop(tile_id, target, zone))
+    }
 
-            // Update grandparent's child pointer or root
-            if let Some(gp_id) = grandparent_id {
-                self.replace_child(&gp_id, &parent_id, &sibling_id);
-            } else {
-                // Parent was root, sibling becomes new root
-                self.root = Some(sibling_id);
+    /// Tile IDs of the subtree starting at `node_id` (in traversal order)
+    pub fn subtree_tiles(&self, node_id: &NodeId) -> Vec<TileId> {
+        let mut tiles = Vec::new();
+        self.collect_tiles(node_id, &mut tiles);
+        tiles
+    }
+
+    /// Whether `node_id` is `ancestor_id` or somewhere below it - O(depth)
+    fn contains_node(&self, ancestor_id: &NodeId, node_id: &NodeId) -> bool {
+        let mut current = Some(node_id);
+        while let Some(id) = current {
+            if id == ancestor_id {
+                return true;
             }
+            current = self.nodes.get(id).and_then(|node| node.parent());
+        }
+        false
+    }
 
-            // Remove parent split node and dragged tile node
-            self.nodes.remove(&parent_id);
-            self.nodes.remove(&dragged_node_id);
-        } else {
-            // Dragged tile was root - just remove it for now
-            self.nodes.remove(&dragged_node_id);
-            self.root = None; // Will be updated below
+    /// Restrict where tiles may be dropped, e.g. to keep an inspector on the right edge
+    ///
+    /// Every drop operation (`insert_tile_with_split`, `insert_new_tile`,
+    /// `dock_at_root`, `dock_new_tile_at_root`, `move_node` and `transfer_tile`) is rejected
+    /// when the policy returns false. The policy isn't serialized, so set it again
+    /// after loading a layout.
+    ///
+    /// # Example
+    /// ```
+    /// # use dioxus_mosaic::{DropZone, MosaicLayout};
+    /// let mut layout = MosaicLayout::new("editor".to_string());
+    /// layout.set_drop_policy(|dragged, target, zone| {
+    ///     dragged.as_str() != "inspector" || (target.is_none() && zone == DropZone::Right)
+    /// });
+    /// assert!(!layout.dock_new_tile_at_root("inspector".to_string(), DropZone::Left));
+    /// assert!(layout.dock_new_tile_at_root("inspector".to_string(), DropZone::Right));
+    /// ```
+    pub fn set_drop_policy(
+        &mut self,
+        policy: impl Fn(&TileId, Option<&TileId>, DropZone) -> bool + Send + Sync + 'static,
+    ) {
+        self.drop_policy = Some(DropPolicy::new(policy));
+    }
+
+    /// Remove the policy set with `set_drop_policy`
+    pub fn clear_drop_policy(&mut self) {
+        self.drop_policy = None;
+    }
+
+    /// Whether `dragged` may be dropped in `zone` of `target`, or along the edge
+    /// of the whole layout given by `zone` when `target` is `None`
+    ///
+    /// Checks that `dragged` is `draggable` if it is in the layout, and that the
+    /// target is in the layout and is a `drop_target` (and `draggable` for
+    /// `DropZone::Center`), then asks the drop policy.
+    /// `dragged` doesn't have to be in the layout, except for `DropZone::Center`
+    /// which swaps it with the target.
+    pub fn can_drop(&self, dragged: &TileId, target: Option<&TileId>, zone: DropZone) -> bool {
+        let dragged_id = self.find_tile(dragged);
+        // The center zone swaps two tiles of the layout
+        if zone == DropZone::Center && (target.is_none() || dragged_id.is_none()) {
+            return false;
+        }
+        if dragged_id.is_some_and(|id| {
+            !self
+                .capabilities_of(&id)
+                .is_some_and(|capabilities| capabilities.draggable)
+        }) {
+            return false;
         }
+        if let Some(target) = target {
+            if target == dragged {
+                return false;
+            }
+            // Swapping moves the target as well
+            let allowed = |capabilities: TileCapabilities| {
+                capabilities.drop_target && (zone != DropZone::Center || capabilities.draggable)
+            };
+            let target_id = self.find_tile(target);
+            if !target_id.is_some_and(|id| self.capabilities_of(&id).is_some_and(allowed)) {
+                return false;
+            }
+        }
+        self.drop_policy
+            .as_ref()
+            .is_none_or(|policy| policy.allows(dragged, target, zone))
+    }
 
-        // Step 2: Split the target tile and insert the dragged tile
-        let target_parent_id = self
-            .nodes
-            .get(&target_node_id)
-            .and_then(|n| n.parent().cloned());
+    /// Insert a tile at the boundary between the two panes of a split
+    ///
+    /// Removes the tile from its current position and places it right after the
+    /// split's first pane, as a sibling rather than inside either pane (e.g.
+    /// between the editor and the terminal). It takes `SPLIT_INSERT_PERCENTAGE`
+    /// of the split, and both panes keep their proportions unless that would
+    /// take a split past its 20..80% limits.
+    ///
+    /// Returns false if the tile or split wasn't found, the split is locked, the
+    /// tile is one of the split's panes, or the drop policy refuses it.
+    pub fn insert_at_split(&mut self, tile_id: &TileId, split_id: &NodeId) -> bool {
+        if !self.can_drop_at_split(tile_id, split_id) {
+            return false;
+        }
+        let node_id = match self.find_tile(tile_id) {
+            Some(id) => id,
+            None => return false,
+        };
+        if !self.detach_node(&node_id) {
+            return false;
+        }
+        self.attach_at_split(&node_id, split_id);
+        true
+    }
 
-        // Create new split node
-        let split_node_id = self.gen_id();
-        let direction = drop_zone.split_direction();
-        let dragged_is_first = drop_zone.dragged_is_first();
+    /// Insert a tile that isn't in the layout yet at the boundary of a split
+    ///
+    /// See `insert_at_split`. Returns false if `new_tile_id` is already in the layout.
+    pub fn insert_new_tile_at_split(&mut self, new_tile_id: TileId, split_id: &NodeId) -> bool {
+        if self.find_tile(&new_tile_id).is_some() || !self.can_drop_at_split(&new_tile_id, split_id)
+        {
+            return false;
+        }
+        let node_id = self.add_detached_tile(new_tile_id);
+        self.attach_at_split(&node_id, split_id);
+        true
+    }
 
-        let (first, second) = if dragged_is_first {
-            (dragged_node_id.clone(), target_node_id.clone())
-        } else {
-            (target_node_id.clone(), dragged_node_id.clone())
+    /// Whether `dragged` may be inserted at the boundary of a split
+    ///
+    /// The split must exist and not be locked, and `dragged` must be `draggable`
+    /// and can't be one of its panes. The drop policy is asked as for a drop
+    /// after the last tile of the first pane and before the first tile of the
+    /// second one.
+    pub fn can_drop_at_split(&self, dragged: &TileId, split_id: &NodeId) -> bool {
+        let (first, second) = match self.nodes.get(split_id) {
+            Some(Node::Split {
+                first,
+                second,
+                locked: false,
+                ..
+            }) => (first, second),
+            _ => return false,
         };
+        if let Some(node_id) = self.find_tile(dragged) {
+            let draggable = self
+                .capabilities_of(&node_id)
+                .is_some_and(|capabilities| capabilities.draggable);
+            if !draggable || &node_id == first || &node_id == second {
+                return false;
+            }
+        }
+        match self.split_neighbours(split_id) {
+            Some([(before, before_zone), (after, after_zone)]) => {
+                self.drop_policy.as_ref().is_none_or(|policy| {
+                    policy.allows(dragged, Some(&before), before_zone)
+                        && policy.allows(dragged, Some(&after), after_zone)
+                })
+            }
+            None => false,
+        }
+    }
 
-        let split_node = Node::Split {
-            id: split_node_id.clone(),
-            direction,
-            first,
+    /// Tiles on each side of a split's boundary, with the zone of each facing it
+    ///
+    /// The last tile of the first pane and the first tile of the second one.
+    pub(crate) fn split_neighbours(&self, split_id: &NodeId) -> Option<[(TileId, DropZone); 2]> {
+        let (direction, first, second) = match self.nodes.get(split_id) {
+            Some(Node::Split {
+                direction,
+                first,
+                second,
+                ..
+            }) => (direction, first, second),
+            _ => return None,
+        };
+        let (before_zone, after_zone) = match direction {
+            SplitDirection::Horizontal => (DropZone::Right, DropZone::Left),
+            SplitDirection::Vertical => (DropZone::Bottom, DropZone::Top),
+        };
+
+        let mut first_tiles = Vec::new();
+        self.collect_tiles(first, &mut first_tiles);
+        let mut second_tiles = Vec::new();
+        self.collect_tiles(second, &mut second_tiles);
+        Some([
+            (first_tiles.pop()?, before_zone),
+            (second_tiles.into_iter().next()?, after_zone),
+        ])
+    }
+
+    /// Link the detached `node_id` between the two panes of `split_id`
+    fn attach_at_split(&mut self, node_id: &NodeId, split_id: &NodeId) {
+        let (direction, second_id, percentage, min, max) = match self.nodes.get(split_id) {
+            Some(Node::Split {
+                direction,
+                second,
+                split_percentage,
+                min_percentage,
+                max_percentage,
+                ..
+            }) => (
+                *direction,
+                second.clone(),
+                *split_percentage,
+                *min_percentage,
+                *max_percentage,
+            ),
+            _ => return,
+        };
+
+        // Both panes give up the same fraction of their size, as far as the
+        // limits of the splits allow
+        let kept = 1.0 - SPLIT_INSERT_PERCENTAGE / 100.0;
+        let outer_percentage = (percentage * kept).clamp(min, max);
+        let inner_percentage =
+            (SPLIT_INSERT_PERCENTAGE / (100.0 - outer_percentage) * 100.0).clamp(20.0, 80.0);
+        let inner_id = self.gen_id();
+        self.nodes.insert(
+            inner_id.clone(),
+            Node::Split {
+                id: inner_id.clone(),
+                direction,
+                first: node_id.clone(),
+                second: second_id.clone(),
+                split_percentage: inner_percentage,
+                parent: Some(split_id.clone()),
+                locked: false,
+                min_percentage: 20.0,
+                max_percentage: 80.0,
+            },
+        );
+        for child_id in [node_id, &second_id] {
+            if let Some(child) = self.nodes.get_mut(child_id) {
+                child.set_parent(Some(inner_id.clone()));
+            }
+        }
+        if let Some(Node::Split {
             second,
-            split_percentage: 50.0, // Default 50/50 split
-            parent: target_parent_id.clone(),
-            locked: false,
-            min_percentage: 20.0,
-            max_percentage: 80.0,
+            split_percentage,
+            ..
+        }) = self.nodes.get_mut(split_id)
+        {
+            *second = inner_id;
+            *split_percentage = outer_percentage;
+        }
+    }
+
+    /// Capabilities of a tile, or `None` if it isn't in the layout
+    pub fn tile_capabilities(&self, tile_id: &TileId) -> Option<TileCapabilities> {
+        self.capabilities_of(&self.find_tile(tile_id)?)
+    }
+
+    /// Change what users can do with a tile
+    ///
+    /// Returns false if the tile wasn't found.
+    pub fn set_tile_capabilities(
+        &mut self,
+        tile_id: &TileId,
+        new_capabilities: TileCapabilities,
+    ) -> bool {
+        let node_id = match self.find_tile(tile_id) {
+            Some(id) => id,
+            None => return false,
         };
+        match self.nodes.get_mut(&node_id) {
+            Some(Node::Tile { capabilities, .. }) => {
+                *capabilities = new_capabilities;
+                true
+            }
+            _ => false,
+        }
+    }
 
-        // Insert split node
-        self.nodes.insert(split_node_id.clone(), split_node);
+    /// Lock or unlock a tile: a locked tile can't be closed nor be dropped on
+    ///
+    /// Shorthand for `TileCapabilities::set_locked`. Returns false if the tile
+    /// wasn't found.
+    pub fn set_tile_locked(&mut self, tile_id: &TileId, locked: bool) -> bool {
+        match self.tile_capabilities(tile_id) {
+            Some(mut capabilities) => {
+                capabilities.set_locked(locked);
+                self.set_tile_capabilities(tile_id, capabilities)
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the divider of a split can be moved
+    ///
+    /// The split must not be locked, and every tile along its divider must be
+    /// `resizable`.
+    pub fn can_resize_split(&self, split_id: &NodeId) -> bool {
+        match self.nodes.get(split_id) {
+            Some(Node::Split {
+                direction,
+                first,
+                second,
+                locked: false,
+                ..
+            }) => {
+                self.edge_is_resizable(first, *direction, false)
+                    && self.edge_is_resizable(second, *direction, true)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether all tiles of a subtree along one of its edges across `direction`
+    /// are resizable; `leading` is the left/top edge, otherwise the right/bottom one
+    fn edge_is_resizable(
+        &self,
+        node_id: &NodeId,
+        direction: SplitDirection,
+        leading: bool,
+    ) -> bool {
+        match self.nodes.get(node_id) {
+            Some(Node::Tile { capabilities, .. }) => capabilities.resizable,
+            Some(Node::Split {
+                direction: split_direction,
+                first,
+                second,
+                ..
+            }) => {
+                if *split_direction != direction {
+                    self.edge_is_resizable(first, direction, leading)
+                        && self.edge_is_resizable(second, direction, leading)
+                } else if leading {
+                    self.edge_is_resizable(first, direction, leading)
+                } else {
+                    self.edge_is_resizable(second, direction, leading)
+                }
+            }
+            None => true,
+        }
+    }
+
+    /// Capabilities of a tile node
+    fn capabilities_of(&self, node_id: &NodeId) -> Option<TileCapabilities> {
+        self.nodes.get(node_id).and_then(Node::capabilities)
+    }
+
+    /// Add a tile node that isn't linked to the tree yet
+    fn add_detached_tile(&mut self, tile_id: TileId) -> NodeId {
+        let node_id = self.gen_id();
+        self.nodes.insert(
+            node_id.clone(),
+            Node::Tile {
+                id: node_id.clone(),
+                tile_id,
+                parent: None,
+                capabilities: TileCapabilities::default(),
+            },
+        );
+        node_id
+    }
 
-        // Update dragged tile node with new parent
-        let mut updated_dragged_node = dragged_tile_node;
-        updated_dragged_node.set_parent(Some(split_node_id.clone()));
-        self.nodes.insert(dragged_node_id.clone(), updated_dragged_node);
+    /// Wrap `target_id` in a new split with the detached `node_id` on the side given by `drop_zone`
+    ///
+    /// `share` is the percentage of the split given to `node_id`.
+    fn attach_beside(
+        &mut self,
+        node_id: &NodeId,
+        target_id: &NodeId,
+        drop_zone: DropZone,
+        share: f64,
+    ) {
+        let target_parent_id = self.nodes.get(target_id).and_then(|n| n.parent().cloned());
+
+        let (first, second, split_percentage) = if drop_zone.dragged_is_first() {
+            (node_id.clone(), target_id.clone(), share)
+        } else {
+            (target_id.clone(), node_id.clone(), 100.0 - share)
+        };
 
-        // Update target tile's parent
-        if let Some(target_node) = self.nodes.get_mut(&target_node_id) {
-            target_node.set_parent(Some(split_node_id.clone()));
+        // Create new split node
+        let split_node_id = self.gen_id();
+        self.nodes.insert(
+            split_node_id.clone(),
+            Node::Split {
+                id: split_node_id.clone(),
+                direction: drop_zone.split_direction(),
+                first,
+                second,
+                split_percentage,
+                parent: target_parent_id.clone(),
+                locked: false,
+                min_percentage: 20.0,
+                max_percentage: 80.0,
+            },
+        );
+        for child_id in [node_id, target_id] {
+            if let Some(child) = self.nodes.get_mut(child_id) {
+                child.set_parent(Some(split_node_id.clone()));
+            }
         }
 
         // Update parent's child pointer or root
         if let Some(parent_id) = target_parent_id {
-            self.replace_child(&parent_id, &target_node_id, &split_node_id);
+            self.replace_child(&parent_id, target_id, &split_node_id);
         } else {
             // Target was root, split becomes new root
             self.root = Some(split_node_id);
         }
+    }
+
+    /// Unlink a node from the tree, keeping it in the map without a parent
+    ///
+    /// The node's sibling takes the place of their parent split, which is removed.
+    /// Returns false if the tree around the node is inconsistent.
+    fn detach_node(&mut self, node_id: &NodeId) -> bool {
+        let parent_id = match self.nodes.get(node_id).and_then(|n| n.parent().cloned()) {
+            Some(id) => id,
+            None => {
+                // The node was the root
+                self.root = None;
+                return true;
+            }
+        };
+
+        // Get sibling node ID
+        let sibling_id = match self.nodes.get(&parent_id) {
+            Some(Node::Split { first, second, .. }) => {
+                if first == node_id {
+                    second.clone()
+                } else {
+                    first.clone()
+                }
+            }
+            _ => return false,
+        };
+
+        // Get grandparent ID
+        let grandparent_id = self.nodes.get(&parent_id).and_then(|n| n.parent().cloned());
+
+        // Update sibling's parent to grandparent
+        if let Some(sibling_node) = self.nodes.get_mut(&sibling_id) {
+            sibling_node.set_parent(grandparent_id.clone());
+        }
+
+        // Update grandparent's child pointer or root
+        if let Some(gp_id) = grandparent_id {
+            self.replace_child(&gp_id, &parent_id, &sibling_id);
+        } else {
+            // Parent was root, sibling becomes new root
+            self.root = Some(sibling_id);
+        }
+
+        // Remove parent split node
+        self.nodes.remove(&parent_id);
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.set_parent(None);
+        }
 
         true
     }
 
-    /// Get all tile IDs in the layout (in traversal order)
-    pub fn get_all_tiles(&self) -> Vec<TileId> {
-        let mut tiles = Vec::new();
-        if let Some(root_id) = &self.root {
-            self.collect_tiles(root_id, &mut tiles);
+    /// Share of the whole mosaic taken by a tile - O(depth)
+    ///
+    /// Returns `(width, height)` as percentages of the mosaic's size, following
+    /// the split percentages from the tile up to the root.
+    pub fn tile_size(&self, tile_id: &TileId) -> Option<(f64, f64)> {
+        let mut child_id = self.find_tile(tile_id)?;
+        let (mut width, mut height) = (100.0, 100.0);
+
+        while let Some(parent_id) = self.nodes.get(&child_id).and_then(|n| n.parent()).cloned() {
+            if let Some(Node::Split {
+                direction,
+                first,
+                split_percentage,
+                ..
+            }) = self.nodes.get(&parent_id)
+            {
+                let share = if first == &child_id {
+                    *split_percentage
+                } else {
+                    100.0 - split_percentage
+                };
+                match direction {
+                    SplitDirection::Horizontal => width = width * share / 100.0,
+                    SplitDirection::Vertical => height = height * share / 100.0,
+                }
+            }
+            child_id = parent_id;
         }
-        tiles
+
+        Some((width, height))
     }
 
     /// Recursively collect tile IDs
@@ -448,38 +1100,76 @@ impl Default for MosaicLayout {
 }
 
 impl MosaicLayout {
-    /// Save layout to localStorage
+    /// Serialize the layout to a versioned JSON payload
     ///
-    /// Serializes the layout to JSON and stores it in localStorage.
-    /// Uses the provided key for storage.
-    pub fn save_to_storage(&self, storage_key: &str) -> Result<(), String> {
-        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+    /// The payload records `LAYOUT_FORMAT_VERSION` so that it can be migrated
+    /// when the layout format changes in a later release.
+    pub fn to_json(&self) -> Result<String, String> {
+        crate::format::encode(self)
+    }
 
-        web_sys::window()
-            .and_then(|w| w.local_storage().ok().flatten())
-            .ok_or_else(|| "localStorage not available".to_string())?
-            .set_item(storage_key, &json)
-            .map_err(|_| "Failed to save to localStorage".to_string())
+    /// Deserialize a layout from a JSON payload
+    ///
+    /// Accepts payloads from any earlier format version (including unversioned
+    /// payloads saved by 0.1 and 0.2) and migrates them to the current format.
+    pub fn from_json(json: &str) -> Result<Self, LoadError> {
+        crate::format::decode(json)
+    }
+
+    /// Save layout to a storage backend
+    ///
+    /// Serializes the layout to versioned JSON and writes it under `storage_key`.
+    pub fn save_to<S: LayoutStorage + ?Sized>(
+        &self,
+        storage: &S,
+        storage_key: &str,
+    ) -> Result<(), StorageError> {
+        let json = self.to_json().map_err(StorageError::Serialization)?;
+        storage.write(storage_key, &json)
+    }
+
+    /// Load layout from a storage backend
+    ///
+    /// Reads, migrates and deserializes the layout stored under `storage_key`.
+    /// Returns a `LoadError` describing why the layout could not be restored.
+    pub fn load_from<S: LayoutStorage + ?Sized>(
+        storage: &S,
+        storage_key: &str,
+    ) -> Result<Self, LoadError> {
+        let json = storage.read(storage_key)?.ok_or(LoadError::NotFound)?;
+        Self::from_json(&json)
+    }
+
+    /// Remove a saved layout from a storage backend
+    pub fn remove_from<S: LayoutStorage + ?Sized>(
+        storage: &S,
+        storage_key: &str,
+    ) -> Result<(), StorageError> {
+        storage.remove(storage_key)
+    }
+
+    /// Save layout to localStorage
+    ///
+    /// Shorthand for `save_to(&LocalStorage, storage_key)`.
+    #[cfg(feature = "web")]
+    pub fn save_to_storage(&self, storage_key: &str) -> Result<(), StorageError> {
+        self.save_to(&LocalStorage, storage_key)
     }
 
     /// Load layout from localStorage
     ///
-    /// Attempts to load and deserialize a layout from localStorage.
-    /// Returns None if the key doesn't exist or deserialization fails.
-    pub fn load_from_storage(storage_key: &str) -> Option<Self> {
-        web_sys::window()
-            .and_then(|w| w.local_storage().ok().flatten())
-            .and_then(|storage| storage.get_item(storage_key).ok().flatten())
-            .and_then(|json_string| serde_json::from_str::<Self>(&json_string).ok())
+    /// Shorthand for `load_from(&LocalStorage, storage_key)`.
+    #[cfg(feature = "web")]
+    pub fn load_from_storage(storage_key: &str) -> Result<Self, LoadError> {
+        Self::load_from(&LocalStorage, storage_key)
     }
 
     /// Clear layout from localStorage
-    pub fn clear_storage(storage_key: &str) -> Result<(), String> {
-        web_sys::window()
-            .and_then(|w| w.local_storage().ok().flatten())
-            .ok_or_else(|| "localStorage not available".to_string())?
-            .remove_item(storage_key)
-            .map_err(|_| "Failed to remove from localStorage".to_string())
+    ///
+    /// Shorthand for `remove_from(&LocalStorage, storage_key)`.
+    #[cfg(feature = "web")]
+    pub fn clear_storage(storage_key: &str) -> Result<(), StorageError> {
+        Self::remove_from(&LocalStorage, storage_key)
     }
 }
 
@@ -494,6 +1184,29 @@ mod tests {
         assert_eq!(layout.get_all_tiles(), vec!["tile1".to_string()]);
     }
 
+    #[test]
+    fn test_tile_size() {
+        let mut layout = MosaicLayout::new("tile1".to_string());
+        assert_eq!(layout.tile_size(&"tile1".to_string()), Some((100.0, 100.0)));
+
+        layout.split_tile(
+            &"tile1".to_string(),
+            SplitDirection::Horizontal,
+            "tile2".to_string(),
+            25.0,
+        );
+        layout.split_tile(
+            &"tile2".to_string(),
+            SplitDirection::Vertical,
+            "tile3".to_string(),
+            40.0,
+        );
+        assert_eq!(layout.tile_size(&"tile1".to_string()), Some((25.0, 100.0)));
+        assert_eq!(layout.tile_size(&"tile2".to_string()), Some((75.0, 40.0)));
+        assert_eq!(layout.tile_size(&"tile3".to_string()), Some((75.0, 60.0)));
+        assert_eq!(layout.tile_size(&"missing".to_string()), None);
+    }
+
     #[test]
     fn test_split_tile() {
         let mut layout = MosaicLayout::new("tile1".to_string());
@@ -537,6 +1250,396 @@ mod tests {
         assert_eq!(layout.get_all_tiles(), Vec::<String>::new());
     }
 
+    /// tile1 | (tile2 / tile3)
+    fn three_tiles() -> MosaicLayout {
+        let mut layout = MosaicLayout::new("tile1".to_string());
+        layout.split_tile(
+            &"tile1".to_string(),
+            SplitDirection::Horizontal,
+            "tile2".to_string(),
+            25.0,
+        );
+        layout.split_tile(
+            &"tile2".to_string(),
+            SplitDirection::Vertical,
+            "tile3".to_string(),
+            70.0,
+        );
+        layout
+    }
+
+    #[test]
+    fn test_insert_tile_with_split() {
+        let mut layout = three_tiles();
+        assert!(layout.insert_tile_with_split(
+            &"tile3".to_string(),
+            &"tile1".to_string(),
+            DropZone::Top
+        ));
+        assert_eq!(layout.get_all_tiles(), vec!["tile3", "tile1", "tile2"]);
+        assert_eq!(layout.nodes.len(), 5);
+        assert_eq!(layout.tile_size(&"tile2".to_string()), Some((75.0, 100.0)));
+        assert_eq!(layout.tile_size(&"tile3".to_string()), Some((25.0, 50.0)));
+        assert!(!layout.insert_tile_with_split(
+            &"tile3".to_string(),
+            &"tile3".to_string(),
+            DropZone::Top
+        ));
+    }
+
+    #[test]
+    fn test_dock_at_root() {
+        let mut layout = three_tiles();
+        assert!(layout.dock_at_root(&"tile3".to_string(), DropZone::Bottom));
+
+        // tile3 spans the whole width below the rest of the layout
+        let root = layout.get_node(layout.root().unwrap()).unwrap();
+        assert!(matches!(
+            root,
+            Node::Split {
+                direction: SplitDirection::Vertical,
+                ..
+            }
+        ));
+        assert_eq!(layout.get_all_tiles(), vec!["tile1", "tile2", "tile3"]);
+        assert_eq!(
+            layout.tile_size(&"tile3".to_string()),
+            Some((100.0, DOCK_PERCENTAGE))
+        );
+        assert_eq!(
+            layout.tile_size(&"tile2".to_string()),
+            Some((75.0, 100.0 - DOCK_PERCENTAGE))
+        );
+        assert_eq!(layout.nodes.len(), 5);
+
+        assert!(layout.dock_at_root(&"tile1".to_string(), DropZone::Left));
+        assert_eq!(
+            layout.tile_size(&"tile1".to_string()),
+            Some((DOCK_PERCENTAGE, 100.0))
+        );
+
+        // A lone tile already is the whole layout
+        let mut single = MosaicLayout::new("tile1".to_string());
+        assert!(!single.dock_at_root(&"tile1".to_string(), DropZone::Bottom));
+        assert!(!layout.dock_at_root(&"missing".to_string(), DropZone::Bottom));
+    }
+
+    #[test]
+    fn test_insert_new_tile() {
+        let mut layout = three_tiles();
+        assert!(layout.insert_new_tile("tile4".to_string(), &"tile1".to_string(), DropZone::Right));
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["tile1", "tile4", "tile2", "tile3"]
+        );
+        assert_eq!(layout.tile_size(&"tile4".to_string()), Some((12.5, 100.0)));
+
+        // Already open, or no target
+        assert!(!layout.insert_new_tile("tile4".to_string(), &"tile2".to_string(), DropZone::Top));
+        assert!(!layout.insert_new_tile(
+            "tile5".to_string(),
+            &"missing".to_string(),
+            DropZone::Top
+        ));
+    }
+
+    #[test]
+    fn test_dock_new_tile_at_root() {
+        let mut layout = three_tiles();
+        assert!(layout.dock_new_tile_at_root("tile4".to_string(), DropZone::Top));
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["tile4", "tile1", "tile2", "tile3"]
+        );
+        assert_eq!(
+            layout.tile_size(&"tile4".to_string()),
+            Some((100.0, DOCK_PERCENTAGE))
+        );
+        assert!(!layout.dock_new_tile_at_root("tile4".to_string(), DropZone::Top));
+
+        let mut empty = MosaicLayout::empty();
+        assert!(empty.dock_new_tile_at_root("tile1".to_string(), DropZone::Left));
+        assert_eq!(empty.get_all_tiles(), vec!["tile1"]);
+        assert_eq!(empty.tile_size(&"tile1".to_string()), Some((100.0, 100.0)));
+    }
+
+    #[test]
+    fn test_transfer_tile() {
+        let mut from = three_tiles();
+        from.set_tile_locked(&"tile2".to_string(), true);
+        let mut to = MosaicLayout::new("main".to_string());
+
+        // Unknown tiles and targets leave both layouts untouched
+        let (from_before, to_before) = (from.clone(), to.clone());
+        let tile2 = "tile2".to_string();
+        assert!(!MosaicLayout::transfer_tile(
+            &mut from,
+            &mut to,
+            &"nope".to_string(),
+            None,
+            DropZone::Left
+        ));
+        assert!(!MosaicLayout::transfer_tile(
+            &mut from,
+            &mut to,
+            &tile2,
+            Some(&"nope".to_string()),
+            DropZone::Left
+        ));
+        assert_eq!((&from, &to), (&from_before, &to_before));
+
+        assert!(MosaicLayout::transfer_tile(
+            &mut from,
+            &mut to,
+            &tile2,
+            Some(&"main".to_string()),
+            DropZone::Left
+        ));
+        assert_eq!(from.get_all_tiles(), vec!["tile1", "tile3"]);
+        assert_eq!(to.get_all_tiles(), vec!["tile2", "main"]);
+        assert_eq!(
+            to.tile_capabilities(&tile2),
+            Some(TileCapabilities::locked())
+        );
+
+        // A tile can't be in both layouts, and an empty layout takes it as its root
+        let mut other = MosaicLayout::new("tile3".to_string());
+        assert!(!MosaicLayout::transfer_tile(
+            &mut from,
+            &mut other,
+            &"tile3".to_string(),
+            None,
+            DropZone::Top
+        ));
+        let mut empty = MosaicLayout::empty();
+        assert!(MosaicLayout::transfer_tile(
+            &mut from,
+            &mut empty,
+            &"tile1".to_string(),
+            None,
+            DropZone::Top
+        ));
+        assert_eq!(empty.get_all_tiles(), vec!["tile1"]);
+        assert_eq!(from.get_all_tiles(), vec!["tile3"]);
+    }
+
+    #[test]
+    fn test_drop_policy() {
+        // tile3 may only be docked on the right edge of the whole layout
+        let mut layout = three_tiles();
+        layout.set_drop_policy(|dragged, target, zone| {
+            dragged.as_str() != "tile3" || (target.is_none() && zone == DropZone::Right)
+        });
+        let before = layout.clone();
+        let (tile1, tile3) = ("tile1".to_string(), "tile3".to_string());
+
+        assert!(!layout.can_drop(&tile3, Some(&tile1), DropZone::Left));
+        assert!(!layout.insert_tile_with_split(&tile3, &tile1, DropZone::Left));
+        assert!(!layout.dock_at_root(&tile3, DropZone::Left));
+        assert_eq!(layout, before);
+        assert!(layout.dock_at_root(&tile3, DropZone::Right));
+        assert!(layout.insert_tile_with_split(&tile1, &"tile2".to_string(), DropZone::Top));
+
+        // Locked targets and the tile itself are never valid targets
+        assert!(!layout.can_drop(&tile1, Some(&tile1), DropZone::Top));
+        layout.set_tile_locked(&tile1, true);
+        assert!(!layout.can_drop(&"tile4".to_string(), Some(&tile1), DropZone::Top));
+
+        layout.clear_drop_policy();
+        assert!(layout.can_drop(&tile3, Some(&"tile2".to_string()), DropZone::Left));
+    }
+
+    #[test]
+    fn test_center_drop_swaps_tiles() {
+        let mut layout = three_tiles();
+        layout.set_tile_locked(&"tile1".to_string(), true);
+        let (tile1, tile3) = ("tile1".to_string(), "tile3".to_string());
+
+        assert!(layout.insert_tile_with_split(&tile3, &"tile2".to_string(), DropZone::Center));
+        assert_eq!(layout.get_all_tiles(), vec!["tile1", "tile3", "tile2"]);
+        assert!(layout.swap_tiles(&tile1, &tile3));
+        assert_eq!(layout.get_all_tiles(), vec!["tile3", "tile1", "tile2"]);
+        assert!(layout
+            .tile_capabilities(&tile1)
+            .is_some_and(|capabilities| capabilities.is_locked()));
+
+        // The center zone only swaps tiles that are both in the layout
+        assert!(!layout.swap_tiles(&tile1, &tile1));
+        assert!(!layout.insert_new_tile("tile4".to_string(), &tile3, DropZone::Center));
+        assert!(!layout.dock_at_root(&tile3, DropZone::Center));
+    }
+
+    #[test]
+    fn test_insert_at_split() {
+        // tile1 | (tile2 / tile3), the inner split at 70%
+        let mut layout = three_tiles();
+        let inner = layout
+            .find_tile(&"tile2".to_string())
+            .and_then(|id| layout.get_node(&id)?.parent().cloned())
+            .unwrap();
+        let height = |layout: &MosaicLayout, tile_id: &str| {
+            let (_, height) = layout.tile_size(&tile_id.to_string()).unwrap();
+            (height * 1000.0).round() / 1000.0
+        };
+
+        assert!(layout.insert_new_tile_at_split("tile4".to_string(), &inner));
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["tile1", "tile2", "tile4", "tile3"]
+        );
+        // tile4 takes a third, tile2 and tile3 keep their 70/30 ratio
+        assert_eq!(height(&layout, "tile2"), 46.667);
+        assert_eq!(height(&layout, "tile4"), 33.333);
+        assert_eq!(height(&layout, "tile3"), 20.0);
+
+        // Panes of the split itself stay where they are
+        assert!(!layout.insert_at_split(&"tile2".to_string(), &inner));
+        assert!(!layout.insert_new_tile_at_split("tile4".to_string(), &inner));
+
+        // Moving tile1 removes the root split, the inner split takes the whole layout
+        assert!(layout.insert_at_split(&"tile1".to_string(), &inner));
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["tile2", "tile1", "tile4", "tile3"]
+        );
+        assert_eq!(
+            layout
+                .tile_size(&"tile1".to_string())
+                .map(|(width, _)| width),
+            Some(100.0)
+        );
+        assert_eq!(layout.root(), Some(&inner));
+    }
+
+    #[test]
+    fn test_insert_at_off_center_split() {
+        // tile1 | (tile2 / tile3), the root split at 25%
+        let mut layout = three_tiles();
+        let root = layout.root().cloned().unwrap();
+        let width = |layout: &MosaicLayout, tile_id: &str| {
+            let (width, _) = layout.tile_size(&tile_id.to_string()).unwrap();
+            (width * 1000.0).round() / 1000.0
+        };
+
+        assert!(layout.insert_new_tile_at_split("tile4".to_string(), &root));
+        // tile1 would get 16.67%, below the 20% minimum of the root split
+        assert_eq!(width(&layout, "tile1"), 20.0);
+        assert_eq!(width(&layout, "tile4"), 33.333);
+        assert_eq!(width(&layout, "tile2"), 46.667);
+        assert!(layout
+            .geometry()
+            .splits
+            .iter()
+            .all(|split| (20.0..=80.0).contains(&split.split_percentage)));
+    }
+
+    #[test]
+    fn test_move_node() {
+        let mut layout = three_tiles();
+        let inner = layout
+            .find_tile(&"tile2".to_string())
+            .and_then(|id| layout.get_node(&id)?.parent().cloned())
+            .unwrap();
+
+        // A split can't be dropped on its own tiles, in the center, or moved when it is the root
+        assert!(!layout.move_node(&inner, Some(&"tile3".to_string()), DropZone::Left));
+        assert!(!layout.move_node(&inner, Some(&"tile1".to_string()), DropZone::Center));
+        assert!(!layout.move_node(&layout.root().cloned().unwrap(), None, DropZone::Left));
+
+        // The split moves as a unit, keeping its 70/30 arrangement
+        assert!(layout.move_node(&inner, Some(&"tile1".to_string()), DropZone::Left));
+        assert_eq!(layout.get_all_tiles(), vec!["tile2", "tile3", "tile1"]);
+        assert_eq!(layout.subtree_tiles(&inner), vec!["tile2", "tile3"]);
+        assert_eq!(layout.tile_size(&"tile2".to_string()), Some((50.0, 70.0)));
+        assert_eq!(layout.nodes.len(), 5);
+
+        // Docking it spans the whole width
+        assert!(layout.move_node(&inner, None, DropZone::Bottom));
+        assert_eq!(layout.get_all_tiles(), vec!["tile1", "tile2", "tile3"]);
+        assert_eq!(layout.tile_size(&"tile3".to_string()), Some((100.0, 9.0)));
+
+        // Every tile of the split must be allowed by the policy
+        layout.set_drop_policy(|dragged, _, zone| {
+            dragged.as_str() != "tile3" || zone != DropZone::Top
+        });
+        assert!(!layout.move_node(&inner, None, DropZone::Top));
+        assert!(layout.can_move_node(&inner, None, DropZone::Left));
+
+        // Tiles move as with `insert_tile_with_split`
+        let tile1 = layout.find_tile(&"tile1".to_string()).unwrap();
+        assert!(layout.move_node(&tile1, Some(&"tile3".to_string()), DropZone::Right));
+        assert_eq!(layout.get_all_tiles(), vec!["tile2", "tile3", "tile1"]);
+    }
+
+    #[test]
+    fn test_tile_capabilities() {
+        // tile1 | (tile2 / tile3)
+        let mut layout = three_tiles();
+        let (tile1, tile2, tile3) = (
+            "tile1".to_string(),
+            "tile2".to_string(),
+            "tile3".to_string(),
+        );
+        let root = layout.root().cloned().unwrap();
+        let inner = layout
+            .find_tile(&tile2)
+            .and_then(|id| layout.get_node(&id)?.parent().cloned())
+            .unwrap();
+        let fixed = TileCapabilities::default()
+            .resizable(false)
+            .splittable(false)
+            .closable(false);
+        assert!(layout.set_tile_capabilities(&tile1, fixed));
+        assert!(!layout.set_tile_capabilities(&"nope".to_string(), fixed));
+
+        // Only the divider along tile1 is fixed
+        assert!(!layout.update_split(&root, 40.0));
+        assert!(layout.update_split(&inner, 40.0));
+        assert!(!layout.split_tile(&tile1, SplitDirection::Vertical, "tile4".to_string(), 50.0));
+        assert!(!layout.close_tile(&tile1));
+
+        // A tile that can't be dragged stays, even with its split
+        let pinned = TileCapabilities::default().draggable(false);
+        layout.set_tile_capabilities(&tile2, pinned);
+        assert!(!layout.insert_tile_with_split(&tile2, &tile1, DropZone::Left));
+        assert!(!layout.dock_at_root(&tile2, DropZone::Bottom));
+        assert!(!layout.move_node(&inner, Some(&tile1), DropZone::Left));
+        assert!(!layout.insert_tile_with_split(&tile3, &tile2, DropZone::Center));
+
+        // Nothing is dropped on a tile that isn't a drop target
+        layout.set_tile_capabilities(&tile3, TileCapabilities::default().drop_target(false));
+        assert!(!layout.insert_new_tile("tile4".to_string(), &tile3, DropZone::Top));
+        assert!(layout.insert_new_tile("tile4".to_string(), &tile2, DropZone::Top));
+        assert_eq!(
+            layout.get_all_tiles(),
+            vec!["tile1", "tile4", "tile2", "tile3"]
+        );
+    }
+
+    #[test]
+    fn test_tile_locking_roundtrip() {
+        let mut layout = three_tiles();
+        let tile1 = "tile1".to_string();
+        let pinned = TileCapabilities::default().draggable(false);
+        layout.set_tile_capabilities(&tile1, pinned);
+
+        assert!(layout.set_tile_locked(&tile1, true));
+        let locked = layout.tile_capabilities(&tile1).unwrap();
+        assert!(locked.is_locked());
+        assert_eq!(locked, TileCapabilities::locked().draggable(false));
+        let node_id = layout.find_tile(&tile1).unwrap();
+        assert!(layout.get_node(&node_id).is_some_and(Node::is_locked));
+        assert!(layout.set_tile_locked(&tile1, false));
+        let unlocked = layout.tile_capabilities(&tile1).unwrap();
+        assert!(!unlocked.is_locked());
+        assert_eq!(unlocked, pinned);
+
+        // Forbidding only one of them doesn't lock the tile
+        assert!(!TileCapabilities::default().closable(false).is_locked());
+        assert!(!TileCapabilities::default().drop_target(false).is_locked());
+        assert!(TileCapabilities::none().is_locked());
+    }
+
     #[test]
     fn test_empty_layout() {
         let layout = MosaicLayout::empty();
@@ -9,8 +9,22 @@
 //! - **Resizable dividers** - Drag to resize panes
 //! - **Dynamic splitting** - Split any tile horizontally or vertically
 //! - **Panel controls** - Close tiles, collapse/expand
-//! - **LocalStorage persistence** - Layout survives page reloads
+//! - **Pluggable persistence** - localStorage, sessionStorage, in-memory or file backends, with versioned migrations
 //! - **Clean builder API** - Easy-to-use tree-like configuration
+//! - **Theming** - Light and dark presets exposed as CSS custom properties, or unstyled
+//!
+//! ## Cargo features
+//!
+//! - `components` - the Dioxus components (`Mosaic`, `SplitPane`, `TilePane`, ...) and
+//!   hooks. They only rely on Dioxus' cross-platform APIs (e.g. `MountedData::get_client_rect`)
+//!   and work with the web, desktop and liveview renderers.
+//! - `web` (default) - `components` plus the `LocalStorage` / `SessionStorage` backends and
+//!   `use_persistent_layout`.
+//!
+//! With `default-features = false` only the renderer-independent core is built:
+//! `MosaicLayout`, the tree and builder APIs, drop-zone math, serialization and the
+//! `LayoutStorage` trait. It has no Dioxus or `web-sys` dependency and builds on any
+//! target, which makes it suitable for validating and transforming layouts on a server.
 //!
 //! ## Quick Start
 //!
@@ -31,34 +45,79 @@
 //!     rsx! {
 //!         Mosaic {
 //!             layout: layout,
-//!             render_tile: move |tile_id| {
-//!                 match tile_id.as_str() {
-//!                     "sidebar" => rsx! { div { "Sidebar" } },
-//!                     "editor" => rsx! { div { "Editor" } },
-//!                     _ => None
-//!                 }
+//!             render_tile: |tile_id: String| match tile_id.as_str() {
+//!                 "sidebar" => Some(rsx! { div { "Sidebar" } }),
+//!                 "editor" => Some(rsx! { div { "Editor" } }),
+//!                 _ => None
 //!             },
 //!         }
 //!     }
 //! }
 //! ```
 
+// Renderer-independent core: layout model, tree API, builder, drop-zone math
+// and serialization. Builds on any target, without Dioxus.
 mod builder;
-mod drag_drop;
+mod drop_zone;
+mod format;
+mod geometry;
 mod layout;
-mod mosaic;
 mod node;
-mod split_pane;
-mod tile_pane;
+mod storage;
+mod theme;
 mod tree_api;
 mod types;
 
+// Dioxus components and hooks
+#[cfg(feature = "components")]
+mod controller;
+#[cfg(feature = "components")]
+mod drag_drop;
+#[cfg(feature = "components")]
+mod mosaic;
+#[cfg(feature = "components")]
+mod persistent_layout;
+#[cfg(feature = "components")]
+mod registry;
+#[cfg(feature = "components")]
+mod split_pane;
+#[cfg(feature = "components")]
+mod tile_handle;
+#[cfg(feature = "components")]
+mod tile_pane;
+
 // Re-export public API
-pub use builder::{tile, MosaicBuilder};
-pub use drag_drop::{DragGhost, DragState, DropZone};
-pub use layout::MosaicLayout;
+pub use builder::{tile, tile_with, MosaicBuilder};
+pub use drop_zone::{calculate_drop_zone, DropPolicy, DropZone, DropZoneGeometry};
+pub use format::{LoadError, LAYOUT_FORMAT_VERSION};
+pub use geometry::{LayoutGeometry, Length, Rect, SplitGeometry, TileGeometry};
+pub use layout::{MosaicLayout, DOCK_PERCENTAGE, SPLIT_INSERT_PERCENTAGE};
+pub use node::TileCapabilities;
+#[cfg(not(target_arch = "wasm32"))]
+pub use storage::FileStorage;
+pub use storage::{LayoutStorage, MemoryStorage, StorageError};
+pub use theme::MosaicTheme;
+pub use tree_api::MosaicNode;
+pub use types::{MosaicMode, NodeId, SplitDirection, TileId};
+
+#[cfg(feature = "components")]
+pub use drag_drop::{
+    CanDrop, DragGhost, DragPayload, DragPreview, DragPreviewOptions, DragSource, DragState,
+    DropTarget, MosaicDragProvider,
+};
+#[cfg(feature = "components")]
 pub use mosaic::Mosaic;
+#[cfg(feature = "web")]
+pub use persistent_layout::use_persistent_layout;
+#[cfg(feature = "components")]
+pub use persistent_layout::{use_persistent_layout_with, PersistentLayout};
+#[cfg(feature = "components")]
+pub use registry::{TileKind, TileRegistry};
+#[cfg(feature = "components")]
 pub use split_pane::SplitPane;
+#[cfg(feature = "web")]
+pub use storage::{LocalStorage, SessionStorage};
+#[cfg(feature = "components")]
+pub use tile_handle::{use_mosaic, DragHandle, TileError, TileHandle};
+#[cfg(feature = "components")]
 pub use tile_pane::TilePane;
-pub use tree_api::MosaicNode;
-pub use types::{NodeId, SplitDirection, TileId};
@@ -1,204 +1,2096 @@
-use dioxus::prelude::*;
-use crate::drag_drop::{DragGhost, DragState};
-use crate::layout::MosaicLayout;
-use crate::node::Node;
+use crate::controller::{LayoutBinding, LayoutController};
+use crate::drag_drop::{
+    drop_payload, drop_payload_at_split, overlay_style, preview_drop, preview_drop_at_split,
+    zone_name, CanDrop, DragGhost, DragPayload, DragPreview, DragPreviewOptions, DragState,
+    DropTarget, SharedDragState,
+};
+use crate::drop_zone::{DropZone, DropZoneGeometry};
+use crate::geometry::SplitGeometry;
+use crate::layout::{MosaicLayout, DOCK_PERCENTAGE};
+use crate::node::TileCapabilities;
+use crate::registry::TileRegistry;
 use crate::split_pane::SplitPane;
+use crate::theme::{use_theme_style, MosaicTheme, ThemeContext};
+use crate::tile_handle::{ActiveTile, MaximizedTile, TileError, TileHandle};
 use crate::tile_pane::TilePane;
-use crate::types::{NodeId, TileId};
+use crate::types::{MosaicMode, SplitDirection, TileId};
+use dioxus::dioxus_core::{SuperFrom, SuperInto};
+use dioxus::prelude::*;
 
 /// Props for the Mosaic component
+///
+/// The render props are [`Callback`]s, so plain closures can be passed. The
+/// `Signal<Box<dyn Fn>>` form used by earlier releases is still accepted.
+///
+/// The layout can be provided in one of three ways:
+/// - `layout`: a signal owned by the caller, updated in place by user interactions
+/// - `initial_layout`: the starting layout of a state owned by `Mosaic`
+/// - `value`: strictly controlled; user interactions only propose a new layout
+///   through `on_change`, and nothing changes until the parent passes it back
+///
+/// The parent can switch between them, or pass another signal, at any time.
+/// Switching to `initial_layout` shows the layout `Mosaic` owns, as it was left.
 #[derive(PartialEq, Clone, Props)]
 pub struct MosaicProps {
     /// Signal containing the MosaicLayout
-    pub layout: Signal<MosaicLayout>,
+    #[props(default = None)]
+    pub layout: Option<Signal<MosaicLayout>>,
+
+    /// Starting layout when `Mosaic` owns the layout state (uncontrolled mode)
+    #[props(default = None)]
+    pub initial_layout: Option<MosaicLayout>,
+
+    /// Layout to render in strictly controlled mode, takes precedence over `layout`
+    #[props(default = None)]
+    pub value: Option<MosaicLayout>,
+
+    /// Called with the layout proposed by every user interaction
+    /// In the other modes it is called after the change has been applied
+    #[props(default = None)]
+    pub on_change: Option<Callback<MosaicLayout>>,
 
     /// Function to render each tile's content
-    /// Takes a TileId and returns an optional Element
-    pub render_tile: Signal<Box<dyn Fn(TileId) -> Option<Element>>>,
+    /// Takes a TileId and returns an optional Element; None defers to the registry
+    #[props(default = None)]
+    pub render_tile: Option<Callback<TileId, Option<Element>>>,
 
     /// Function to render each tile's title
-    /// Takes a TileId and returns an Element for the title
-    pub render_title: Signal<Box<dyn Fn(TileId) -> Element>>,
+    /// Takes a TileId and returns an Element for the title; defaults to the
+    /// registry's title, then to the tile ID
+    #[props(default = None)]
+    pub render_title: Option<Callback<TileId, Element>>,
+
+    /// Tile kinds, rendering every tile that `render_tile` doesn't
+    #[props(default = None)]
+    pub registry: Option<TileRegistry>,
 
     /// Optional function to render empty state when no tiles are open
     /// If not provided, a default message will be shown
+    #[props(default = None::<Callback<(), Element>>)]
+    pub render_empty_state: Option<Callback<(), Element>>,
+
+    /// Optional function to render a tile's whole header
+    /// Takes a TileId and its TileHandle; returning None hides the header, in which
+    /// case a `DragHandle` inside the tile can be used to drag it
+    #[props(default = None)]
+    pub render_header: Option<Callback<(TileId, TileHandle), Option<Element>>>,
+
+    /// Optional function to render the controls on the right of a tile's header
+    /// Takes a TileId and its TileHandle, and replaces the default close button
+    #[props(default = None)]
+    pub render_controls: Option<Callback<(TileId, TileHandle), Element>>,
+
+    /// Colors and dimensions of the mosaic
+    /// Falls back to a `MosaicTheme` provided as context, then to the dark preset
+    #[props(default = None)]
+    pub theme: Option<MosaicTheme>,
+
+    /// Optional function to render a tile whose content panicked or returned an error
+    /// The `TileError` can reload or close the tile; defaults to a message with both actions.
+    /// Panics are only contained where they unwind: on wasm (`panic = "abort"`) a
+    /// panicking tile still stops the app, so tiles should return errors instead.
     #[props(default = None)]
-    pub render_empty_state: Option<Signal<Box<dyn Fn() -> Element>>>,
+    pub render_error: Option<Callback<TileError, Element>>,
+
+    /// Optional function to render tiles that neither `render_tile` nor the registry know
+    /// Takes a TileId and its TileHandle; defaults to a message with a close button
+    #[props(default = None)]
+    pub render_missing: Option<Callback<(TileId, TileHandle), Element>>,
+
+    /// Optional function to render the preview following the cursor during a drag
+    /// Takes the dragged tile ID and the hovered drop target; replaces the ghost
+    #[props(default = None)]
+    pub render_drag_preview: Option<Callback<DragPreview, Element>>,
+
+    /// Offset of the drag ghost from the cursor, and whether it shows a scaled
+    /// snapshot of the dragged tile
+    #[props(default = None)]
+    pub drag_preview: Option<DragPreviewOptions>,
+
+    /// Optional function deciding where tiles may be dropped
+    /// Takes the dragged tile ID, the target tile (None for the edges of the whole
+    /// mosaic) and the drop zone; zones it refuses aren't shown. Applies on top of
+    /// the tiles' capabilities and the layout's own `set_drop_policy`
+    #[props(default = None)]
+    pub can_drop: Option<CanDrop>,
+
+    /// How tiles are divided into drop zones: edge bands (the default) or
+    /// triangles, with an optional center zone swapping the two tiles
+    #[props(default = None)]
+    pub drop_zones: Option<DropZoneGeometry>,
+
+    /// Draw the layout a drop would produce over the tiles while dragging,
+    /// with the proportions it would have
+    #[props(default = false)]
+    pub live_preview: bool,
+
+    /// `MosaicMode::ReadOnly` to present the layout without letting users
+    /// resize, drag, drop or close anything; can be switched at any time
+    #[props(default)]
+    pub mode: MosaicMode,
+}
+
+/// Marker for the `Signal<Box<dyn Fn>>` render props of earlier releases
+#[doc(hidden)]
+pub struct BoxedRenderFn;
+
+impl SuperFrom<Signal<Box<dyn Fn(TileId) -> Option<Element>>>, BoxedRenderFn>
+    for Option<Callback<TileId, Option<Element>>>
+{
+    fn super_from(render: Signal<Box<dyn Fn(TileId) -> Option<Element>>>) -> Self {
+        Some(Callback::new(move |tile_id| (render.read())(tile_id)))
+    }
+}
+
+impl SuperFrom<Signal<Box<dyn Fn(TileId) -> Element>>, BoxedRenderFn>
+    for Option<Callback<TileId, Element>>
+{
+    fn super_from(render: Signal<Box<dyn Fn(TileId) -> Element>>) -> Self {
+        Some(Callback::new(move |tile_id| (render.read())(tile_id)))
+    }
+}
+
+impl SuperFrom<Option<Signal<Box<dyn Fn() -> Element>>>, BoxedRenderFn>
+    for Option<Callback<(), Element>>
+{
+    fn super_from(render: Option<Signal<Box<dyn Fn() -> Element>>>) -> Self {
+        render.map(|render| Callback::new(move |()| (render.read())()))
+    }
+}
+
+impl SuperFrom<Signal<Box<dyn Fn() -> Element>>, BoxedRenderFn> for Option<Callback<(), Element>> {
+    fn super_from(render: Signal<Box<dyn Fn() -> Element>>) -> Self {
+        Some(render).super_into()
+    }
+}
+
+/// Resolves tile content, titles and headers from the callbacks and the registry
+#[derive(Clone, Copy)]
+pub(crate) struct TileRenderer {
+    render_tile: Option<Callback<TileId, Option<Element>>>,
+    render_title: Option<Callback<TileId, Element>>,
+    render_header: Option<Callback<(TileId, TileHandle), Option<Element>>>,
+    render_controls: Option<Callback<(TileId, TileHandle), Element>>,
+    render_error: Option<Callback<TileError, Element>>,
+    render_missing: Option<Callback<(TileId, TileHandle), Element>>,
+    pub(crate) registry: Memo<Option<TileRegistry>>,
+}
+
+impl TileRenderer {
+    /// `render_tile` first, then the registry (and its fallback), then `render_missing`
+    fn content(&self, tile_id: TileId) -> Element {
+        if let Some(render_tile) = self.render_tile {
+            if let Some(content) = render_tile.call(tile_id.clone()) {
+                return content;
+            }
+        }
+        let registered = match &*self.registry.read() {
+            Some(registry) => registry.try_render(tile_id.clone()),
+            None => None,
+        };
+        if let Some(content) = registered {
+            return content;
+        }
+
+        let handle = TileHandle::from_context(tile_id.clone());
+        match self.render_missing {
+            Some(render_missing) => render_missing.call((tile_id, handle)),
+            None => rsx! { MissingTile { handle } },
+        }
+    }
+
+    /// `render_error`, or a message with reload and close buttons
+    fn error(&self, error: TileError) -> Element {
+        match self.render_error {
+            Some(render_error) => render_error.call(error),
+            None => rsx! { TileErrorMessage { error } },
+        }
+    }
+
+    /// `render_title` first, then the registry, then the tile ID
+    fn title(&self, tile_id: TileId) -> Element {
+        if let Some(render_title) = self.render_title {
+            return render_title.call(tile_id);
+        }
+        match &*self.registry.read() {
+            Some(registry) => registry.render_title(tile_id),
+            None => rsx! { span { "{tile_id}" } },
+        }
+    }
+
+    /// Title of what is dragged: the tile, or all the tiles of a dragged split
+    fn drag_title(&self, payload: &DragPayload, layout: &MosaicLayout) -> Element {
+        match payload {
+            DragPayload::Split(node_id) => rsx! {
+                for (index, tile_id) in layout.subtree_tiles(node_id).into_iter().enumerate() {
+                    span {
+                        key: "{tile_id}",
+                        if index > 0 { " + " }
+                        {self.title(tile_id.clone())}
+                    }
+                }
+            },
+            _ => self.title(payload.display_id().to_string()),
+        }
+    }
 }
 
 /// Main mosaic component
 ///
 /// Renders a tiling window manager with resizable splits and dynamic tiles.
 ///
+/// The markup is derived from the `layout` signal alone (elements are only
+/// measured in event handlers), so it can be server-side rendered and hydrated.
+/// The server and the client's first render must see the same layout: restore
+/// a persisted layout after hydration rather than while rendering on the server.
+///
+/// Without a `layout`, `initial_layout` or `value`, the mosaic starts empty.
+///
 /// # Example
 /// ```ignore
-/// let render_fn = use_signal(|| Box::new(move |tile_id: String| {
-///     match tile_id.as_str() {
+/// Mosaic {
+///     layout: layout_signal,
+///     render_tile: |tile_id: TileId| match tile_id.as_str() {
 ///         "editor" => Some(rsx! { EditorPanel {} }),
 ///         "sidebar" => Some(rsx! { SidebarPanel {} }),
 ///         _ => None
-///     }
-/// }) as Box<dyn Fn(String) -> Option<Element>>);
+///     },
+/// }
+/// ```
 ///
+/// Strictly controlled, refusing to close the editor:
+/// ```ignore
+/// let mut layout = use_signal(|| initial);
 /// Mosaic {
-///     layout: layout_signal,
-///     render_tile: render_fn,
+///     value: layout(),
+///     on_change: move |proposed: MosaicLayout| {
+///         if proposed.find_tile(&"editor".to_string()).is_some() {
+///             layout.set(proposed);
+///         }
+///     },
 /// }
 /// ```
+#[allow(non_snake_case)]
 pub fn Mosaic(props: MosaicProps) -> Element {
-    let layout = props.layout;
+    // State used when neither `layout` nor `value` is passed
+    let initial_layout = props.initial_layout.clone();
+    let owned = use_signal(|| initial_layout.unwrap_or_else(MosaicLayout::empty));
+    let value = use_memo(use_reactive((&props.value,), |(value,)| {
+        value.unwrap_or_else(MosaicLayout::empty)
+    }));
 
-    // Initialize drag state
-    let drag_state = use_signal(DragState::new);
+    // Initialize drag state, shared with the drag sources of a `MosaicDragProvider`
+    let shared_drag = try_use_context::<SharedDragState>();
+    let drag_state = use_hook(|| match shared_drag {
+        Some(shared) => shared.0,
+        None => Signal::new(DragState::new()),
+    });
 
-    // Provide layout signal, drag state, and render functions to all child components via context
-    use_context_provider(|| layout);
+    // Edit or read-only, followed by every component through the controller
+    let mode = use_memo(use_reactive((&props.mode,), |(mode,)| mode));
+
+    // Where the layout comes from and where changes go, followed when the
+    // parent switches modes or passes another signal
+    let binding = use_memo(use_reactive(
+        (
+            &props.value.is_some(),
+            &props.layout,
+            &props.on_change,
+            &props.can_drop,
+        ),
+        move |(controlled, layout, on_change, can_drop)| {
+            let binding = if controlled {
+                LayoutBinding::controlled(value, on_change)
+            } else {
+                LayoutBinding::signal(layout.unwrap_or(owned), on_change)
+            };
+            binding.with_can_drop(can_drop)
+        },
+    ));
+
+    // Provide the layout, drag state, and render functions to all child components via context
+    let controller = use_context_provider(|| LayoutController::new(binding).with_mode(mode));
+    // Read from the prop, so the components below follow a new mode in the same render
+    let read_only = props.mode.is_read_only();
+    let layout = controller.layout();
     use_context_provider(|| drag_state);
-    use_context_provider(|| props.render_tile);
-    use_context_provider(|| props.render_title);
+    let registry = use_memo(use_reactive((&props.registry,), |(registry,)| registry));
+    let renderer = use_context_provider(|| TileRenderer {
+        render_tile: props.render_tile,
+        render_title: props.render_title,
+        render_header: props.render_header,
+        render_controls: props.render_controls,
+        render_error: props.render_error,
+        render_missing: props.render_missing,
+        registry,
+    });
 
-    let root_id = layout.read().root().cloned();
+    // Tile toggled with `TileHandle::toggle_maximize`
+    let mut maximized = use_context_provider(|| MaximizedTile(Signal::new(None))).0;
+
+    // Restore the layout once the maximized tile is gone, which in strictly
+    // controlled mode is only when the parent accepted closing it
+    use_effect(move || {
+        let layout = controller.layout();
+        let layout = layout.read();
+        let closed = maximized
+            .peek()
+            .as_ref()
+            .is_some_and(|tile_id| layout.find_tile(tile_id).is_none());
+        if closed {
+            maximized.set(None);
+        }
+    });
+
+    // Tile last clicked or focused, see `TileHandle::is_active`
+    use_context_provider(|| ActiveTile(Signal::new(None)));
+
+    // Resolve the theme and share it with the components below
+    let app_theme = try_use_context::<MosaicTheme>();
+    let theme = props.theme.clone().or(app_theme).unwrap_or_default();
+    let theme = use_memo(use_reactive((&theme,), |(theme,)| theme));
+    use_context_provider(|| ThemeContext(theme));
+    let styled = !theme.read().unstyled;
+
+    // A maximized tile is rendered on its own, in place of the root. Tiles
+    // follow a divider being dragged before the layout is updated on release.
+    let geometry = {
+        let layout = layout.read();
+        let resized = controller.resized_layout();
+        let layout = resized.as_ref().unwrap_or(&layout);
+        maximized
+            .read()
+            .as_ref()
+            .and_then(|tile_id| layout.find_tile(tile_id))
+            .or_else(|| layout.root().cloned())
+            .map(|root| layout.geometry_of(&root))
+    };
+
+    // Tiles are rendered in a flat list keyed by tile ID, in an order that doesn't
+    // depend on the tree: moving a tile in the layout only changes its position,
+    // so its component state (and DOM) survives the move
+    let (mut tiles, splits) = match geometry {
+        Some(geometry) => (geometry.tiles, geometry.splits),
+        None => (Vec::new(), Vec::new()),
+    };
+    tiles.sort_by(|a, b| a.tile_id.cmp(&b.tile_id));
+
+    // Layout the drop would produce if it happened now (`live_preview`)
+    let layout_preview = if props.live_preview && maximized.read().is_none() {
+        let state = drag_state.read();
+        match (&state.payload, &state.drop_target) {
+            (Some(payload), Some(drop_target)) => {
+                let dropped_tile_ids = payload.tile_ids(&layout.read());
+                let registry = registry.read().clone();
+                match drop_target {
+                    DropTarget::Tile(target, zone) => preview_drop(
+                        controller,
+                        registry,
+                        payload,
+                        state.source,
+                        Some(target),
+                        *zone,
+                    ),
+                    DropTarget::Edge(zone) => {
+                        preview_drop(controller, registry, payload, state.source, None, *zone)
+                    }
+                    DropTarget::Divider(split_id) => {
+                        preview_drop_at_split(controller, registry, payload, state.source, split_id)
+                    }
+                }
+                .map(|preview| (dropped_tile_ids, preview.geometry().tiles))
+            }
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    // Drag preview: `render_drag_preview`, or the ghost, optionally showing a
+    // snapshot of the dragged tile
+    let drag_preview = props.drag_preview.clone().unwrap_or_default();
+    let (preview, snapshot) = match drag_state.read().payload.clone() {
+        Some(payload) => {
+            let snapshot = match (&payload, drag_preview.snapshot_scale) {
+                (DragPayload::Tile(tile_id), Some(scale)) => {
+                    Some(rsx! { TileSnapshot { tile_id: tile_id.clone(), scale } })
+                }
+                _ => None,
+            };
+            let preview = props.render_drag_preview.map(|render| {
+                render.call(DragPreview {
+                    tile_id: payload.display_id().to_string(),
+                    hover_target: drag_state.read().hover_target.clone(),
+                    payload,
+                })
+            });
+            (preview, snapshot)
+        }
+        None => (None, None),
+    };
 
     rsx! {
         div {
-            class: "mosaic-container",
-            style: "width: 100%; height: 100%; position: relative;",
+            class: if read_only { "mosaic-container read-only" } else { "mosaic-container" },
+            // Tiles are positioned against the container, so this is emitted even when unstyled
+            style: if styled {
+                format!("{}width: 100%; height: 100%; position: relative;", theme.read().css_variables())
+            } else {
+                "position: relative;".to_string()
+            },
 
             // Render content based on whether layout is empty
-            if let Some(root) = root_id {
-                // Recursively render from root
-                MosaicNode {
-                    node_id: root,
+            if !tiles.is_empty() {
+                for tile in tiles {
+                    div {
+                        key: "{tile.tile_id}",
+                        class: "mosaic-tile-slot",
+                        "data-tile-id": "{tile.tile_id}",
+                        style: "position: absolute; {tile.rect.to_css(TRACK)}",
+                        MosaicTile {
+                            tile_id: tile.tile_id.clone(),
+                            // Nothing can be done with the tiles of a read-only mosaic
+                            capabilities: if read_only { TileCapabilities::none() } else { tile.capabilities },
+                            drop_zones: props.drop_zones.unwrap_or_default(),
+                        }
+                    }
+                }
+
+                // Dividers, drawn over the tiles
+                for split in splits {
+                    MosaicSplit {
+                        key: "{split.node_id}",
+                        split: split.clone(),
+                        read_only,
+                    }
+                }
+
+                // Ghost of the resulting layout, over the tiles
+                if let Some((dropped_tile_ids, preview_tiles)) = layout_preview {
+                    div {
+                        class: "mosaic-layout-preview",
+                        // Covers the container, so it is emitted even when unstyled
+                        style: "position: absolute; top: 0; left: 0; right: 0; bottom: 0; pointer-events: none; z-index: 15;",
+                        for tile in preview_tiles {
+                            div {
+                                key: "{tile.tile_id}",
+                                class: if dropped_tile_ids.contains(&tile.tile_id) {
+                                    "mosaic-layout-preview-tile dropped"
+                                } else {
+                                    "mosaic-layout-preview-tile"
+                                },
+                                style: if styled {
+                                    overlay_style(
+                                        &tile.rect.to_css(TRACK),
+                                        dropped_tile_ids.contains(&tile.tile_id),
+                                    ) + " padding: 0.5rem; color: var(--mosaic-header-text); font-size: 0.875rem;"
+                                } else {
+                                    format!("position: absolute; {}", tile.rect.to_css(TRACK))
+                                },
+                                {renderer.title(tile.tile_id.clone())}
+                            }
+                        }
+                    }
+                }
+
+                // Edges of the whole mosaic, to dock the dragged tile
+                if drag_state.read().is_dragging() {
+                    RootDropZones {}
                 }
             } else {
+                // An empty mosaic still takes drops, e.g. to bring back the last
+                // tile moved out of it
+                if drag_state.read().is_dragging() {
+                    RootDropZones {}
+                }
+
                 // Render empty state
                 if let Some(render_empty) = props.render_empty_state {
-                    {(render_empty.read())()}
+                    {render_empty.call(())}
                 } else {
                     // Default empty state
                     div {
-                        style: "
+                        class: "mosaic-empty-state",
+                        style: if styled {
+                            "
                             display: flex;
                             justify-content: center;
                             align-items: center;
                             height: 100%;
-                            color: #888;
+                            color: var(--mosaic-muted-text);
                             font-size: 1rem;
-                        ",
+                            "
+                        } else {
+                            ""
+                        },
                         "No panels open"
                     }
                 }
             }
 
-            // Render drag ghost when dragging
-            if drag_state.read().is_dragging() {
+            // Render drag ghost when dragging, unless another mosaic or the
+            // `MosaicDragProvider` sharing the drag state draws it
+            if drag_state.read().is_dragging() && (shared_drag.is_none() || drag_state.read().source == Some(controller)) {
                 DragGhost {
                     drag_state: drag_state,
-                    render_title: props.render_title,
+                    title: drag_state.read().payload.as_ref().map(|payload| renderer.drag_title(payload, &layout.read())),
+                    content: snapshot,
+                    preview: preview,
+                    offset: drag_preview.offset,
                 }
             }
         }
     }
 }
 
-/// Internal component for rendering a single node (recursively)
+/// CSS size of a divider track: the divider and the gap on each side of it
+///
+/// Unstyled mosaics don't declare the variables, so they default to 0.
+const TRACK: &str = "(var(--mosaic-gap, 0px) * 2 + var(--mosaic-divider-width, 0px))";
+
+/// Thickness of the drop zones along the edges of the mosaic
+const ROOT_DROP_ZONE_SIZE: &str = "16px";
+
+/// Internal component rendering drop zones along the edges of the whole mosaic
+///
+/// Dropping a tile on one of them docks it with `MosaicLayout::dock_at_root`,
+/// e.g. as a full-width panel at the bottom.
 #[component]
-fn MosaicNode(node_id: NodeId) -> Element {
-    let mut layout = use_context::<Signal<MosaicLayout>>();
-    let render_tile = use_context::<Signal<Box<dyn Fn(TileId) -> Option<Element>>>>();
-    let render_title = use_context::<Signal<Box<dyn Fn(TileId) -> Element>>>();
-    let node = layout.read().get_node(&node_id).cloned();
-
-    match node {
-        Some(Node::Tile {
-            tile_id,
-            locked,
-            ..
-        }) => {
-            // Clone tile_id for use in multiple closures
-            let tile_id_for_horizontal = tile_id.clone();
-            let tile_id_for_vertical = tile_id.clone();
-            let tile_id_for_close = tile_id.clone();
-
-            // Render title and content
-            let title = (render_title.read())(tile_id.clone());
-            let content = (render_tile.read())(tile_id.clone());
-
-            rsx! {
-                TilePane {
-                    tile_id: tile_id.clone(),
-                    title_component: title,
-                    locked: locked,
-                    on_split_horizontal: move |_| {
-                        let new_tile_id = format!("{}_new", tile_id_for_horizontal);
-                        layout.write().split_tile(
-                            &tile_id_for_horizontal,
-                            crate::types::SplitDirection::Horizontal,
-                            new_tile_id,
-                            50.0
-                        );
-                    },
-                    on_split_vertical: move |_| {
-                        let new_tile_id = format!("{}_new", tile_id_for_vertical);
-                        layout.write().split_tile(
-                            &tile_id_for_vertical,
-                            crate::types::SplitDirection::Vertical,
-                            new_tile_id,
-                            50.0
-                        );
-                    },
-                    on_close: move |_| {
-                        layout.write().close_tile(&tile_id_for_close);
-                    },
+fn RootDropZones() -> Element {
+    let controller = use_context::<LayoutController>();
+    let renderer = use_context::<TileRenderer>();
+    let mut drag_state = use_context::<Signal<DragState>>();
+    let mut hovered = use_signal(|| None::<DropZone>);
+    let theme = use_theme_style();
 
-                    {content}
-                }
+    // Only the edges where the dragged payload may be docked
+    let zones = drag_state
+        .read()
+        .payload_for(controller)
+        .map_or(Vec::new(), |payload| {
+            [
+                DropZone::Top,
+                DropZone::Bottom,
+                DropZone::Left,
+                DropZone::Right,
+            ]
+            .into_iter()
+            .filter(|zone| controller.can_drop(payload, None, *zone))
+            .collect()
+        });
+
+    rsx! {
+        for zone in zones {
+            div {
+                key: "{zone:?}",
+                class: if hovered() == Some(zone) {
+                    format!("root-drop-zone root-drop-zone-{} active", zone_name(zone))
+                } else {
+                    format!("root-drop-zone root-drop-zone-{}", zone_name(zone))
+                },
+                // Geometry, so it is emitted even when unstyled
+                style: {
+                    let edge = edge_area(zone, ROOT_DROP_ZONE_SIZE);
+                    let color = if theme.styled && hovered() == Some(zone) {
+                        "background-color: var(--mosaic-drop-zone-active-border);"
+                    } else {
+                        ""
+                    };
+                    format!("position: absolute; {edge} z-index: 20; {color}")
+                },
+                ondragover: move |evt| {
+                    evt.prevent_default(); // Required to allow drop
+                    if hovered() != Some(zone) {
+                        hovered.set(Some(zone));
+                    }
+                    if drag_state.read().drop_target != Some(DropTarget::Edge(zone)) {
+                        drag_state.write().hover_edge(zone);
+                    }
+                },
+                ondragleave: move |_evt| {
+                    hovered.set(None);
+                    if drag_state.read().drop_target == Some(DropTarget::Edge(zone)) {
+                        drag_state.write().clear_hover();
+                    }
+                },
+                ondrop: move |evt| {
+                    evt.prevent_default();
+                    let payload = drag_state.read().payload.clone();
+                    if let Some(payload) = payload {
+                        let source = drag_state.read().source;
+                        drop_payload(controller, renderer.registry.read().clone(), &payload, source, None, zone);
+                    }
+                    drag_state.write().end_drag();
+                    hovered.set(None);
+                },
             }
         }
 
-        Some(Node::Split {
-            direction,
-            first,
-            second,
-            split_percentage,
-            ..
-        }) => {
-            // Render a split with two children
-            let node_id_for_resize = node_id.clone();
-
-            rsx! {
-                SplitPane {
-                    direction: direction,
-                    initial_size: split_percentage,
-                    min_size: 20.0,
-                    max_size: 80.0,
-                    on_resize: Some(EventHandler::new(move |new_pos: f64| {
-                        layout.write().update_split(&node_id_for_resize, new_pos);
-                    })),
-
-                    first_pane: rsx! {
-                        MosaicNode {
-                            node_id: first.clone(),
-                        }
+        // Preview of the area the docked tile will take
+        if let Some(zone) = hovered() {
+            div {
+                class: "root-drop-zone-preview",
+                style: {
+                    let area = edge_area(zone, &format!("{DOCK_PERCENTAGE}%"));
+                    if theme.styled {
+                        overlay_style(&area, true)
+                    } else {
+                        format!("position: absolute; {area}")
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// CSS placing an area `size` thick along the edge of the mosaic given by `zone`
+fn edge_area(zone: DropZone, size: &str) -> String {
+    match zone {
+        DropZone::Top => format!("top: 0; left: 0; right: 0; height: {size};"),
+        DropZone::Bottom => format!("bottom: 0; left: 0; right: 0; height: {size};"),
+        DropZone::Left => format!("top: 0; bottom: 0; left: 0; width: {size};"),
+        DropZone::Right => format!("top: 0; bottom: 0; right: 0; width: {size};"),
+        // Not an edge: the whole mosaic
+        DropZone::Center => "top: 0; left: 0; right: 0; bottom: 0;".to_string(),
+    }
+}
+
+/// Internal component rendering a tile, positioned by its slot in `Mosaic`
+#[component]
+fn MosaicTile(
+    tile_id: TileId,
+    capabilities: TileCapabilities,
+    drop_zones: DropZoneGeometry,
+) -> Element {
+    let controller = use_context::<LayoutController>();
+    let renderer = use_context::<TileRenderer>();
+
+    let tile_id_for_close = tile_id.clone();
+
+    // Render title (the content is rendered below the error boundary)
+    let title = renderer.title(tile_id.clone());
+
+    // Custom header: None keeps the default one, Some(None) hides it
+    let handle = TileHandle::from_context(tile_id.clone());
+    let header = renderer
+        .render_header
+        .map(|render| render.call((tile_id.clone(), handle.clone())));
+    let controls = renderer
+        .render_controls
+        .map(|render| render.call((tile_id.clone(), handle.clone())));
+
+    rsx! {
+        TilePane {
+            tile_id: tile_id.clone(),
+            title_component: title,
+            capabilities: capabilities,
+            drop_zones: drop_zones,
+            show_header: !matches!(header, Some(None)),
+            header: header.flatten(),
+            controls: controls,
+            on_close: move |_| {
+                controller.update(|layout| layout.close_tile(&tile_id_for_close));
+            },
+
+            // A panicking or failing tile only takes its own content down
+            ErrorBoundary {
+                handle_error: move |context: ErrorContext| {
+                    match TileError::new(handle.clone(), context) {
+                        Some(error) => renderer.error(error),
+                        None => VNode::empty(),
+                    }
+                },
+                TileContent { tile_id: tile_id.clone() }
+            }
+        }
+    }
+}
+
+/// Internal component rendering a tile's content, inside the tile's error boundary
+#[component]
+fn TileContent(tile_id: TileId) -> Element {
+    let renderer = use_context::<TileRenderer>();
+    renderer.content(tile_id)
+}
+
+/// Internal component showing a static copy of a tile's content in the drag ghost
+///
+/// The tile's DOM is cloned when the ghost is mounted, so its components aren't
+/// run a second time. Only the web renderer gives access to the DOM; elsewhere
+/// the snapshot stays empty.
+#[component]
+fn TileSnapshot(tile_id: TileId, scale: f64) -> Element {
+    rsx! {
+        div {
+            class: "drag-ghost-snapshot",
+            // Geometry, so it is emitted even when unstyled
+            style: "position: absolute; top: 0; left: 0; width: {100.0 / scale}%; height: {100.0 / scale}%; transform: scale({scale}); transform-origin: top left;",
+            onmounted: move |evt| copy_tile_content(&evt.data(), &tile_id),
+        }
+    }
+}
+
+/// Append a copy of the content of `tile_id`, from the mosaic `snapshot` is in, to `snapshot`
+#[cfg(feature = "web")]
+fn copy_tile_content(snapshot: &MountedData, tile_id: &str) {
+    let Some(snapshot) = snapshot.downcast::<web_sys::Element>() else {
+        return;
+    };
+    let tile_id = tile_id.replace('\\', "\\\\").replace('"', "\\\"");
+    let selector =
+        format!(":scope > .mosaic-tile-slot[data-tile-id=\"{tile_id}\"] .mosaic-tile-content");
+    let content = snapshot
+        .closest(".mosaic-container")
+        .ok()
+        .flatten()
+        .and_then(|container| container.query_selector(&selector).ok().flatten());
+    if let Some(copy) = content.and_then(|content| content.clone_node_with_deep(true).ok()) {
+        let _ = snapshot.append_child(&copy);
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn copy_tile_content(_snapshot: &MountedData, _tile_id: &str) {}
+
+/// Default `render_error`
+#[component]
+fn TileErrorMessage(error: TileError) -> Element {
+    let theme = use_theme_style();
+    let reload = error.clone();
+    let close = error.clone();
+
+    rsx! {
+        div {
+            class: "mosaic-tile-error",
+            style: if theme.styled { "padding: 1rem; color: var(--mosaic-muted-text);" } else { "" },
+            p { "This tile failed to render." }
+            pre {
+                style: if theme.styled { "color: var(--mosaic-danger); white-space: pre-wrap;" } else { "" },
+                "{error.error()}"
+            }
+            button { class: "mosaic-tile-reload", onclick: move |_| reload.reload(), "Reload tile" }
+            if error.handle().capabilities().is_some_and(|capabilities| capabilities.closable) {
+                button { class: "mosaic-tile-close", onclick: move |_| close.close(), "Close tile" }
+            }
+        }
+    }
+}
+
+/// Default `render_missing`
+#[component]
+fn MissingTile(handle: TileHandle) -> Element {
+    let theme = use_theme_style();
+    let close = handle.clone();
+
+    rsx! {
+        div {
+            class: "mosaic-missing-tile",
+            style: if theme.styled { "padding: 1rem; color: var(--mosaic-muted-text);" } else { "" },
+            p { "Unknown tile: {handle.tile_id()}" }
+            if handle.capabilities().is_some_and(|capabilities| capabilities.closable) {
+                button { class: "mosaic-tile-close", onclick: move |_| close.close(), "Close tile" }
+            }
+        }
+    }
+}
+
+/// Internal component rendering the divider of a split over the area it covers
+///
+/// The frame lets pointer events through to the tiles below, except on the
+/// divider itself (and over the whole split while it is being dragged).
+#[component]
+fn MosaicSplit(split: SplitGeometry, read_only: bool) -> Element {
+    let controller = use_context::<LayoutController>();
+    let renderer = use_context::<TileRenderer>();
+    let mut drag_state = use_context::<Signal<DragState>>();
+    let node_id = split.node_id.clone();
+
+    // The divider accepts the dragged payload when it can be inserted there;
+    // tiles from other mosaics are only dropped on tiles and edges
+    let accepts_drop = {
+        let drag = drag_state.read();
+        drag.payload_for(controller).is_some_and(|payload| {
+            drag.source.is_none_or(|source| source == controller)
+                && controller.can_drop_at_split(payload, &node_id)
+        })
+    };
+    let on_divider_drop = accepts_drop.then(|| {
+        let node_id = node_id.clone();
+        EventHandler::new(move |_| {
+            let (payload, source) = {
+                let drag = drag_state.read();
+                (drag.payload.clone(), drag.source)
+            };
+            if let Some(payload) = payload {
+                drop_payload_at_split(
+                    controller,
+                    renderer.registry.read().clone(),
+                    &payload,
+                    source,
+                    &node_id,
+                );
+            }
+            drag_state.write().end_drag();
+        })
+    });
+    // Hovering the divider previews the insert (`live_preview`)
+    let on_divider_hover = accepts_drop.then(|| {
+        let node_id = node_id.clone();
+        EventHandler::new(move |entered: bool| {
+            let target = DropTarget::Divider(node_id.clone());
+            if entered {
+                drag_state.write().hover_divider(node_id.clone());
+            } else if drag_state.read().drop_target == Some(target) {
+                drag_state.write().clear_hover();
+            }
+        })
+    });
+
+    // Grab handle at the start of the divider, to drag the split with all its
+    // tiles; the root split has nowhere to go
+    let (movable, resizable) = {
+        let layout = controller.layout();
+        let layout = layout.read();
+        (
+            !read_only && layout.root() != Some(&node_id),
+            !read_only && layout.can_resize_split(&node_id),
+        )
+    };
+    let theme = use_theme_style();
+    let handle_style = {
+        let divider_center = format!(
+            "calc({}% + var(--mosaic-gap, 0px) + var(--mosaic-divider-width, 0px) / 2)",
+            split.split_percentage
+        );
+        // Geometry, so it is emitted even when unstyled
+        let position = match split.direction {
+            SplitDirection::Horizontal => {
+                format!("left: {divider_center}; top: 4px; transform: translateX(-50%);")
+            }
+            SplitDirection::Vertical => {
+                format!("top: {divider_center}; left: 4px; transform: translateY(-50%);")
+            }
+        };
+        if theme.styled {
+            format!(
+                "
+                position: absolute;
+                {position}
+                pointer-events: auto;
+                z-index: 5;
+                width: 16px;
+                height: 16px;
+                display: flex;
+                align-items: center;
+                justify-content: center;
+                font-size: 10px;
+                line-height: 1;
+                color: var(--mosaic-divider-indicator);
+                background-color: var(--mosaic-divider);
+                border-radius: var(--mosaic-control-radius);
+                cursor: grab;
+                user-select: none;
+            "
+            )
+        } else {
+            format!("position: absolute; {position} pointer-events: auto;")
+        }
+    };
+    let drag_node_id = node_id.clone();
+    let resizing_node_id = node_id.clone();
+
+    // Tiles would otherwise stay where an unmounted divider left them
+    use_drop({
+        let node_id = node_id.clone();
+        move || controller.cancel_resize(&node_id)
+    });
+
+    rsx! {
+        div {
+            class: "mosaic-split-frame",
+            style: "position: absolute; {split.rect.to_css(TRACK)} pointer-events: none;",
+            if movable {
+                div {
+                    class: "mosaic-split-handle",
+                    title: "Drag to move this group",
+                    draggable: "true",
+                    ondragstart: move |evt| {
+                        let position = evt.client_coordinates();
+                        drag_state.write().start_split_drag(controller, drag_node_id.clone(), position.x, position.y);
                     },
+                    ondrag: move |evt| {
+                        let position = evt.client_coordinates();
 
-                    second_pane: rsx! {
-                        MosaicNode {
-                            node_id: second.clone(),
+                        // Drag events can fire at 0,0 when the drag ends
+                        if position.x != 0.0 || position.y != 0.0 {
+                            drag_state.write().update_position(position.x, position.y);
                         }
                     },
+                    ondragend: move |_evt| {
+                        drag_state.write().end_drag();
+                    },
+                    style: "{handle_style}",
+                    "⠿"
                 }
             }
+            SplitPane {
+                direction: split.direction,
+                initial_size: split.split_percentage,
+                min_size: 20.0,
+                max_size: 80.0,
+                on_resizing: move |new_pos: f64| controller.resize_live(&resizing_node_id, new_pos),
+                on_resize: Some(EventHandler::new(move |new_pos: f64| {
+                    controller.resize(&node_id, new_pos);
+                })),
+                first_pane: rsx! {},
+                second_pane: rsx! {},
+                on_divider_drop,
+                on_divider_hover,
+                resizable,
+            }
         }
+    }
+}
 
-        None => {
-            // Node not found (shouldn't happen)
-            rsx! {
-                div {
-                    style: "color: red; padding: 1rem;",
-                    "Error: Node not found"
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{tile, MosaicBuilder};
+    use crate::drag_drop::{DragPayload, DragSource, MosaicDragProvider};
+    use crate::registry::TileKind;
+    use crate::tile_handle::{use_mosaic, DragHandle};
+
+    fn nested_layout_app() -> Element {
+        themed_layout_app(None)
+    }
+
+    /// sidebar | (editor / terminal)
+    fn nested_layout() -> MosaicLayout {
+        MosaicBuilder::horizontal()
+            .left(tile("sidebar"))
+            .right(
+                MosaicBuilder::vertical()
+                    .top(tile("editor"))
+                    .bottom(tile("terminal"))
+                    .split(70.0)
+                    .build_tree(),
+            )
+            .split(25.0)
+            .build()
+    }
+
+    /// Uses the `Signal<Box<dyn Fn>>` render props of earlier releases
+    fn themed_layout_app(theme: Option<MosaicTheme>) -> Element {
+        let layout = use_signal(nested_layout);
+        let render_tile = use_signal(|| {
+            Box::new(|tile_id: TileId| Some(rsx! { p { "{tile_id} content" } }))
+                as Box<dyn Fn(TileId) -> Option<Element>>
+        });
+        let render_title = use_signal(|| {
+            Box::new(|tile_id: TileId| rsx! { span { "{tile_id}" } })
+                as Box<dyn Fn(TileId) -> Element>
+        });
+
+        rsx! {
+            Mosaic {
+                layout,
+                render_tile,
+                render_title,
+                theme,
+            }
+        }
+    }
+
+    fn render_to_string(pre_render: bool) -> String {
+        let mut dom = VirtualDom::new(nested_layout_app);
+        dom.rebuild_in_place();
+        if pre_render {
+            dioxus_ssr::pre_render(&dom)
+        } else {
+            dioxus_ssr::render(&dom)
+        }
+    }
+
+    /// Compare against `src/snapshots/<name>.html`, or rewrite it when
+    /// `UPDATE_SNAPSHOTS` is set
+    fn assert_snapshot(name: &str, actual: &str) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/snapshots")
+            .join(format!("{}.html", name));
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        pretty_assertions::assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_ssr_nested_layout_snapshot() {
+        assert_snapshot("ssr_nested_layout", &render_to_string(false));
+    }
+
+    #[test]
+    fn test_ssr_output_is_stable_for_hydration() {
+        // The server render and the client's first render must produce the same tree
+        let first = render_to_string(true);
+        let second = render_to_string(true);
+        assert_eq!(first, second);
+
+        // Split positions come straight from the layout
+        assert!(first.contains("25% "));
+        assert!(first.contains("70% "));
+        for tile_id in ["sidebar", "editor", "terminal"] {
+            assert!(first.contains(&format!("{} content", tile_id)));
+        }
+    }
+
+    fn render_with_theme(theme: MosaicTheme) -> String {
+        let mut dom = VirtualDom::new_with_props(themed_layout_app, Some(theme));
+        dom.rebuild_in_place();
+        dioxus_ssr::render(&dom)
+    }
+
+    #[test]
+    fn test_theme_is_emitted_as_css_variables() {
+        let html = render_with_theme(MosaicTheme::light());
+        assert!(html.contains("--mosaic-tile-background: #ffffff;"));
+        assert!(html.contains("background-color: var(--mosaic-tile-background)"));
+        assert!(!html.contains("#1a1d24"));
+    }
+
+    #[test]
+    fn test_unstyled_theme_only_emits_geometry() {
+        let html = render_with_theme(MosaicTheme::unstyled());
+        assert!(!html.contains("--mosaic-tile-background"));
+        assert!(!html.contains("background-color"));
+        assert!(html.contains("class=\"mosaic-tile-pane\""));
+        // Split positions are still needed to lay the panes out
+        assert!(html.contains("grid-template-columns: 25% "));
+    }
+
+    /// What the components of a `Harness` app saw
+    #[derive(Default)]
+    struct Probes {
+        /// Latest handle of each tile, from `TileProbe` or a render prop
+        handles: Vec<TileHandle>,
+        drag: Option<Signal<DragState>>,
+        controller: Option<LayoutController>,
+        /// Layout signals, the harness' own first
+        layouts: Vec<Signal<MosaicLayout>>,
+        mode: Option<Signal<MosaicMode>>,
+        /// Layouts passed to `on_change`
+        proposals: Vec<MosaicLayout>,
+        /// Errors passed to `render_error`
+        errors: Vec<TileError>,
+    }
+
+    thread_local! {
+        static PROBES: std::cell::RefCell<Probes> = std::cell::RefCell::default();
+    }
+
+    fn probe(record: impl FnOnce(&mut Probes)) {
+        PROBES.with_borrow_mut(record);
+    }
+
+    fn probe_handle(handle: TileHandle) {
+        probe(|probes| {
+            probes
+                .handles
+                .retain(|probed| probed.tile_id() != handle.tile_id());
+            probes.handles.push(handle);
+        });
+    }
+
+    /// Layout signal whose value the harness can read back
+    fn use_probed_layout(initial: impl FnOnce() -> MosaicLayout) -> Signal<MosaicLayout> {
+        let layout = use_signal(initial);
+        use_hook(|| probe(|probes| probes.layouts.push(layout)));
+        layout
+    }
+
+    /// Tile content recording its handle, drag state and controller, and
+    /// showing its size
+    #[component]
+    fn TileProbe() -> Element {
+        let tile = use_mosaic();
+        probe_handle(tile.clone());
+        probe(|probes| {
+            probes.drag = Some(use_context::<Signal<DragState>>());
+            probes.controller = Some(use_context::<LayoutController>());
+        });
+        let (width, height) = tile.size().unwrap_or_default();
+        let active = tile.is_active();
+        rsx! { "{tile.tile_id()} {width}x{height} active={active}" }
+    }
+
+    /// State a `Harness` app renders its mosaic from
+    struct Fixture {
+        layout: Signal<MosaicLayout>,
+        mode: Signal<MosaicMode>,
+        /// Renders every tile as a `TileProbe`
+        registry: TileRegistry,
+    }
+
+    #[derive(Clone)]
+    struct HarnessProps {
+        layout: MosaicLayout,
+        mode: MosaicMode,
+        mosaic: fn(&Fixture) -> Element,
+    }
+
+    fn harness_app(props: HarnessProps) -> Element {
+        let layout = use_probed_layout(|| props.layout.clone());
+        let mode = use_signal(|| props.mode);
+        use_hook(|| probe(|probes| probes.mode = Some(mode)));
+        let registry = use_hook(|| TileRegistry::new().fallback(|_| rsx! { TileProbe {} }));
+        (props.mosaic)(&Fixture {
+            layout,
+            mode,
+            registry,
+        })
+    }
+
+    /// A plain `Mosaic` of `TileProbe`s
+    fn plain_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic { layout: fixture.layout, registry: fixture.registry.clone() }
+        }
+    }
+
+    /// An app rendering a mosaic, and what its components probed
+    struct Harness {
+        dom: VirtualDom,
+    }
+
+    impl Harness {
+        fn mount(props: HarnessProps) -> Self {
+            PROBES.set(Probes::default());
+            let mut dom = VirtualDom::new_with_props(harness_app, props);
+            dom.rebuild_in_place();
+            dom.render_immediate_to_vec();
+            Self { dom }
+        }
+
+        /// Render `mosaic` from `layout`, in edit mode
+        fn new(layout: MosaicLayout, mosaic: fn(&Fixture) -> Element) -> Self {
+            Self::mount(HarnessProps {
+                layout,
+                mode: MosaicMode::Edit,
+                mosaic,
+            })
+        }
+
+        /// `nested_layout` in a plain `Mosaic`
+        fn nested() -> Self {
+            Self::new(nested_layout(), plain_mosaic)
+        }
+
+        fn html(&self) -> String {
+            dioxus_ssr::render(&self.dom)
+        }
+
+        /// Render once what changed
+        fn render(&mut self) -> String {
+            self.dom.process_events();
+            self.dom.render_immediate_to_vec();
+            self.html()
+        }
+
+        /// Render changes that go through the parent first (controlled mode),
+        /// and run the effects
+        fn settle(&mut self) -> String {
+            self.render();
+            self.render();
+            self.dom.process_events();
+            self.html()
+        }
+
+        /// Run `action` in the runtime, then render once
+        fn update(&mut self, action: impl FnOnce()) -> String {
+            self.dom.in_runtime(action);
+            self.render()
+        }
+
+        /// Start dragging `tile_id` with its handle
+        fn drag(&mut self, tile_id: &str) -> String {
+            let handle = self.handle(tile_id);
+            self.update(|| handle.start_drag(100.0, 100.0))
+        }
+
+        /// Change the drag state, e.g. the hovered target, then render
+        fn hover(&mut self, change: impl FnOnce(&mut DragState)) -> String {
+            let mut drag_state = self.drag_state();
+            self.update(|| change(&mut drag_state.write()))
+        }
+
+        fn in_runtime<R>(&self, f: impl FnOnce() -> R) -> R {
+            self.dom.in_runtime(f)
+        }
+
+        /// Run `f` in the app's scope, e.g. to create signals
+        fn in_scope<R>(&self, f: impl FnOnce() -> R) -> R {
+            self.dom.in_scope(ScopeId::APP, f)
+        }
+
+        fn handle(&self, tile_id: &str) -> TileHandle {
+            PROBES.with_borrow(|probes| {
+                probes
+                    .handles
+                    .iter()
+                    .find(|handle| handle.tile_id() == tile_id)
+                    .cloned()
+                    .unwrap()
+            })
+        }
+
+        fn drag_state(&self) -> Signal<DragState> {
+            PROBES.with_borrow(|probes| probes.drag.unwrap())
+        }
+
+        fn controller(&self) -> LayoutController {
+            PROBES.with_borrow(|probes| probes.controller.unwrap())
+        }
+
+        fn mode(&self) -> Signal<MosaicMode> {
+            PROBES.with_borrow(|probes| probes.mode.unwrap())
+        }
+
+        fn layout_signal(&self, index: usize) -> Signal<MosaicLayout> {
+            PROBES.with_borrow(|probes| probes.layouts[index])
+        }
+
+        /// Current value of the harness' layout signal
+        fn layout(&self) -> MosaicLayout {
+            self.layout_at(0)
+        }
+
+        fn layout_at(&self, index: usize) -> MosaicLayout {
+            let layout = self.layout_signal(index);
+            self.in_runtime(|| layout.read().clone())
+        }
+
+        fn proposals(&self) -> Vec<MosaicLayout> {
+            PROBES.with_borrow(|probes| probes.proposals.clone())
+        }
+    }
+
+    fn content_with_grip(tile_id: TileId) -> Option<Element> {
+        Some(rsx! {
+            if tile_id == "terminal" {
+                DragHandle { "grip" }
             }
+            p { "{tile_id} content" }
+        })
+    }
+
+    /// Custom headers, and a headerless terminal
+    fn custom_headers(fixture: &Fixture) -> Element {
+        // Plain closures, and no `render_title`: titles default to the tile ID
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                render_tile: content_with_grip,
+                render_header: |(tile_id, _handle): (TileId, TileHandle)| {
+                    (tile_id != "terminal").then(|| rsx! { span { class: "custom-header", "{tile_id}" } })
+                },
+            }
+        }
+    }
+
+    fn custom_controls(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                render_tile: content_with_grip,
+                render_controls: |(_tile_id, handle): (TileId, TileHandle)| {
+                    probe_handle(handle);
+                    rsx! { button { class: "refresh", "⟳" } }
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_headers_and_headerless_tiles() {
+        let html = Harness::new(nested_layout(), custom_headers).html();
+        assert_eq!(html.matches("class=\"custom-header\"").count(), 2);
+        assert_eq!(html.matches("class=\"mosaic-tile-header\"").count(), 2);
+        assert!(html.contains("class=\"mosaic-drag-handle\""));
+    }
+
+    #[test]
+    fn test_custom_controls_drive_the_tile() {
+        let mut mosaic = Harness::new(nested_layout(), custom_controls);
+        let html = mosaic.html();
+        assert_eq!(html.matches("class=\"refresh\"").count(), 3);
+        assert!(!html.contains("mosaic-tile-close"));
+        assert!(html.contains("<span>sidebar</span>"));
+
+        // Maximizing shows the tile on its own, toggling again restores the layout
+        let editor = mosaic.handle("editor");
+        let html = mosaic.update(|| editor.toggle_maximize());
+        assert!(html.contains("editor content"));
+        assert!(!html.contains("sidebar content"));
+        let html = mosaic.update(|| editor.toggle_maximize());
+        assert!(html.contains("sidebar content"));
+
+        let html = mosaic.update(|| assert!(editor.split(SplitDirection::Horizontal, "logs")));
+        assert!(html.contains("logs content"));
+
+        let sidebar = mosaic.handle("sidebar");
+        let html = mosaic.update(|| sidebar.close());
+        assert!(!html.contains("sidebar content"));
+    }
+
+    fn registry_mosaic(fixture: &Fixture) -> Element {
+        let registry = TileRegistry::new()
+            .register(
+                "sidebar",
+                TileKind::new(|_| rsx! { "file tree" })
+                    .title("Files")
+                    .icon("📁"),
+            )
+            .register(
+                "editor",
+                TileKind::new(|tile_id| rsx! { "editing {tile_id}" }),
+            )
+            .fallback(|tile_id| rsx! { "no renderer for {tile_id}" });
+
+        rsx! {
+            Mosaic { layout: fixture.layout, registry }
+        }
+    }
+
+    #[test]
+    fn test_registry_renders_tiles_by_kind() {
+        let html = Harness::new(nested_layout(), registry_mosaic).html();
+        assert!(html.contains("file tree"));
+        assert!(html.contains("📁"));
+        assert!(html.contains("<span>Files</span>"));
+        assert!(html.contains("editing editor"));
+        // Unregistered kinds go to the fallback, titled by their ID
+        assert!(html.contains("no renderer for terminal"));
+        assert!(html.contains("<span>terminal</span>"));
+    }
+
+    #[test]
+    fn test_use_mosaic_is_bound_to_the_current_tile() {
+        let mut mosaic = Harness::nested();
+        let html = mosaic.html();
+        assert!(html.contains("sidebar 25x100 active=false"));
+        assert!(html.contains("editor 75x70 active=false"));
+
+        let editor = mosaic.handle("editor");
+        let html = mosaic.update(|| editor.focus());
+        assert!(html.contains("editor 75x70 active=true"));
+        assert!(html.contains("class=\"mosaic-tile-pane active\""));
+
+        let html = mosaic.update(|| editor.close());
+        assert!(!html.contains("editor 75x70"));
+        assert!(html.contains("terminal 75x100"));
+    }
+
+    fn propose(layout: MosaicLayout) {
+        probe(|probes| probes.proposals.push(layout));
+    }
+
+    /// Mosaic owning its layout, reporting its changes
+    fn uncontrolled_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                initial_layout: nested_layout(),
+                on_change: propose,
+                registry: fixture.registry.clone(),
+            }
+        }
+    }
+
+    /// Parent accepting every proposal that keeps the editor open
+    fn controlled_mosaic(fixture: &Fixture) -> Element {
+        let mut layout = fixture.layout;
+        let on_change = move |proposed: MosaicLayout| {
+            propose(proposed.clone());
+            if proposed.find_tile(&"editor".to_string()).is_some() {
+                layout.set(proposed);
+            }
+        };
+        rsx! {
+            Mosaic { value: layout(), on_change, registry: fixture.registry.clone() }
+        }
+    }
+
+    #[test]
+    fn test_uncontrolled_mosaic_owns_its_layout() {
+        let mut mosaic = Harness::new(nested_layout(), uncontrolled_mosaic);
+        assert!(mosaic.html().contains("terminal 75x30"));
+
+        let terminal = mosaic.handle("terminal");
+        mosaic.in_runtime(|| terminal.close());
+        let html = mosaic.settle();
+        assert!(!html.contains("terminal 75x30"));
+        assert!(html.contains("editor 75x100"));
+        // `on_change` is notified of the applied change
+        assert_eq!(mosaic.proposals().len(), 1);
+    }
+
+    #[test]
+    fn test_controlled_mosaic_only_proposes_changes() {
+        let mut mosaic = Harness::new(nested_layout(), controlled_mosaic);
+
+        // The parent rejects closing the maximized editor: the proposal is made,
+        // nothing changes and the editor stays maximized
+        let editor = mosaic.handle("editor");
+        mosaic.in_runtime(|| {
+            editor.toggle_maximize();
+            editor.close();
+        });
+        let html = mosaic.settle();
+        assert!(html.contains("editor 75x70"));
+        assert!(mosaic.in_runtime(|| editor.is_maximized()));
+        assert!(mosaic.proposals()[0]
+            .find_tile(&"editor".to_string())
+            .is_none());
+        mosaic.in_runtime(|| editor.toggle_maximize());
+
+        // ...and accepts closing the maximized terminal, which restores the layout
+        let terminal = mosaic.handle("terminal");
+        mosaic.in_runtime(|| {
+            terminal.toggle_maximize();
+            terminal.close();
+        });
+        let html = mosaic.settle();
+        assert!(!html.contains("terminal 75x30"));
+        assert!(html.contains("editor 75x100"));
+        assert!(html.contains("sidebar 25x100"));
+        assert!(mosaic.in_runtime(|| !terminal.is_maximized()));
+        assert_eq!(mosaic.proposals().len(), 2);
+    }
+
+    /// Where `switching_mosaic` takes its layout from
+    #[derive(Clone, Copy, PartialEq)]
+    enum LayoutSource {
+        Layout,
+        OtherLayout,
+        Value,
+        Owned,
+    }
+
+    thread_local! {
+        static LAYOUT_SOURCE: std::cell::Cell<Option<Signal<LayoutSource>>> = const { std::cell::Cell::new(None) };
+    }
+
+    /// Mosaic whose parent switches between two signals, strictly controlled
+    /// mode and uncontrolled mode
+    fn switching_mosaic(fixture: &Fixture) -> Element {
+        let source = use_signal(|| LayoutSource::Layout);
+        LAYOUT_SOURCE.set(Some(source));
+        let other = use_probed_layout(|| MosaicLayout::new("monitor".to_string()));
+        let layout = match source() {
+            LayoutSource::Layout => Some(fixture.layout),
+            LayoutSource::OtherLayout => Some(other),
+            _ => None,
+        };
+        let value = (source() == LayoutSource::Value).then(nested_layout);
+        rsx! {
+            Mosaic {
+                layout,
+                value,
+                initial_layout: nested_layout(),
+                on_change: propose,
+                registry: fixture.registry.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_mosaic_follows_a_new_layout_source() {
+        let mut mosaic = Harness::new(nested_layout(), switching_mosaic);
+        let mut source = LAYOUT_SOURCE.get().unwrap();
+        assert!(mosaic.html().contains("sidebar 25x100"));
+
+        // Another signal is rendered and changed in place of the first one
+        let html = mosaic.update(|| source.set(LayoutSource::OtherLayout));
+        assert!(html.contains("monitor 100x100"));
+        assert!(!html.contains("sidebar"));
+        let monitor = mosaic.handle("monitor");
+        let html = mosaic.update(|| assert!(monitor.split(SplitDirection::Horizontal, "logs")));
+        assert!(html.contains("logs 50x100"));
+        assert_eq!(mosaic.layout_at(1).get_all_tiles(), ["monitor", "logs"]);
+        assert_eq!(mosaic.layout(), nested_layout());
+
+        // Strictly controlled, changes are only proposed
+        let html = mosaic.update(|| source.set(LayoutSource::Value));
+        assert!(html.contains("terminal 75x30"));
+        let terminal = mosaic.handle("terminal");
+        mosaic.in_runtime(|| terminal.close());
+        assert!(mosaic.settle().contains("terminal 75x30"));
+        assert_eq!(mosaic.proposals().len(), 2);
+
+        // Uncontrolled, `Mosaic` applies them to its own layout
+        mosaic.update(|| source.set(LayoutSource::Owned));
+        let terminal = mosaic.handle("terminal");
+        mosaic.in_runtime(|| terminal.close());
+        let html = mosaic.settle();
+        assert!(html.contains("editor 75x100"));
+        assert_eq!(mosaic.layout(), nested_layout());
+        assert_eq!(mosaic.layout_at(1).get_all_tiles(), ["monitor", "logs"]);
+    }
+
+    thread_local! {
+        /// Counter signal of the editor of `counting_mosaic`, and its mounts
+        static EDITOR_COUNT: std::cell::Cell<Option<Signal<usize>>> = const { std::cell::Cell::new(None) };
+        static EDITOR_MOUNTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    #[component]
+    fn CountingTile(tile_id: TileId) -> Element {
+        let count = use_signal(|| 0);
+        if tile_id == "editor" {
+            use_hook(|| EDITOR_MOUNTS.set(EDITOR_MOUNTS.get() + 1));
+            EDITOR_COUNT.set(Some(count));
         }
+        rsx! { "{tile_id} count={count}" }
+    }
+
+    fn counting_mosaic(fixture: &Fixture) -> Element {
+        let registry =
+            use_hook(|| TileRegistry::new().fallback(|tile_id| rsx! { CountingTile { tile_id } }));
+        rsx! {
+            Mosaic { layout: fixture.layout, registry }
+        }
+    }
+
+    #[test]
+    fn test_tile_state_survives_moves() {
+        EDITOR_MOUNTS.set(0);
+        let mut mosaic = Harness::new(nested_layout(), counting_mosaic);
+        let html = mosaic.update(|| EDITOR_COUNT.get().unwrap().set(1));
+        assert!(html.contains("editor count=1"));
+
+        // Move the editor from the right column to below the sidebar
+        let mut layout = mosaic.layout_signal(0);
+        let html = mosaic.update(|| {
+            assert!(layout.write().insert_tile_with_split(
+                &"editor".to_string(),
+                &"sidebar".to_string(),
+                DropZone::Bottom,
+            ));
+        });
+        assert_eq!(
+            mosaic.layout().tile_size(&"editor".to_string()),
+            Some((25.0, 50.0))
+        );
+
+        // Same component instance: the signal kept its value and it wasn't remounted
+        assert!(html.contains("editor count=1"));
+        assert_eq!(EDITOR_MOUNTS.get(), 1);
+    }
+
+    #[test]
+    fn test_tiles_follow_a_dragged_divider() {
+        let mut mosaic = Harness::nested();
+        let controller = mosaic.controller();
+        let original = mosaic.layout();
+        let root = original.root().cloned().unwrap();
+        let mut resized = original.clone();
+        resized.update_split(&root, 40.0);
+        let slot_style = |layout: &MosaicLayout, tile_id: &str| {
+            let geometry = layout.geometry();
+            let tile = geometry
+                .tiles
+                .iter()
+                .find(|tile| tile.tile_id == tile_id)
+                .unwrap();
+            format!("position: absolute; {}", tile.rect.to_css(TRACK))
+        };
+
+        // While the divider is dragged the tiles move with it, the layout stays
+        let html = mosaic.update(|| controller.resize_live(&root, 40.0));
+        assert!(!html.contains(&slot_style(&original, "editor")));
+        assert!(html.contains(&slot_style(&resized, "sidebar")));
+        assert!(html.contains(&slot_style(&resized, "editor")));
+        assert!(html.contains("sidebar 25x100"));
+
+        // Releasing it commits the position to the layout
+        let html = mosaic.update(|| assert!(controller.resize(&root, 40.0)));
+        assert!(html.contains(&slot_style(&resized, "editor")));
+        assert!(html.contains("sidebar 40x100"));
+    }
+
+    thread_local! {
+        /// Whether the terminal of `failing_mosaic` panics while rendering
+        static TERMINAL_FAILS: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
+    }
+
+    /// Failing terminal and unknown sidebar
+    fn failing_content(tile_id: TileId) -> Option<Element> {
+        if tile_id == "terminal" && TERMINAL_FAILS.get() {
+            panic!("terminal crashed");
+        }
+        (tile_id != "sidebar").then(|| rsx! { p { "{tile_id} content" } })
+    }
+
+    fn failing_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic { layout: fixture.layout, render_tile: failing_content }
+        }
+    }
+
+    fn failing_mosaic_with_custom_views(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                render_tile: failing_content,
+                render_error: |error: TileError| {
+                    probe(|probes| probes.errors.push(error.clone()));
+                    rsx! { span { "custom error for {error.tile_id()}" } }
+                },
+                render_missing: |(tile_id, handle): (TileId, TileHandle)| {
+                    probe_handle(handle);
+                    rsx! { span { "no {tile_id} here" } }
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn test_failing_and_missing_tiles_are_contained() {
+        TERMINAL_FAILS.set(true);
+        let html = Harness::new(nested_layout(), failing_mosaic).html();
+        assert!(html.contains("class=\"mosaic-tile-error\""));
+        assert!(html.contains("Reload tile"));
+        assert!(html.contains("<p>editor content</p>"));
+        assert!(html.contains("Unknown tile: sidebar"));
+        assert!(!html.contains("Node not found"));
+    }
+
+    #[test]
+    fn test_render_error_can_reload_the_tile() {
+        TERMINAL_FAILS.set(true);
+        let mut mosaic = Harness::new(nested_layout(), failing_mosaic_with_custom_views);
+        let html = mosaic.html();
+        assert!(html.contains("custom error for terminal"));
+        assert!(html.contains("no sidebar here"));
+
+        let error = PROBES.with_borrow(|probes| probes.errors.last().cloned().unwrap());
+        assert_eq!(error.tile_id(), "terminal");
+
+        // Once the cause is fixed, reloading renders the content again
+        TERMINAL_FAILS.set(false);
+        let html = mosaic.update(|| error.reload());
+        assert!(html.contains("<p>terminal content</p>"));
+        assert!(!html.contains("custom error"));
+    }
+
+    #[test]
+    fn test_render_missing_can_close_the_tile() {
+        TERMINAL_FAILS.set(true);
+        let mut mosaic = Harness::new(nested_layout(), failing_mosaic_with_custom_views);
+        let sidebar = mosaic.handle("sidebar");
+        let html = mosaic.update(|| sidebar.close());
+        assert!(!html.contains("no sidebar here"));
+    }
+
+    #[test]
+    fn test_root_drop_zones_show_while_dragging() {
+        let mut mosaic = Harness::nested();
+        assert!(!mosaic.html().contains("root-drop-zone"));
+
+        let html = mosaic.drag("terminal");
+        for zone in ["top", "bottom", "left", "right"] {
+            assert!(html.contains(&format!("class=\"root-drop-zone root-drop-zone-{zone}\"")));
+        }
+    }
+
+    /// A palette of drag sources next to the mosaic
+    fn palette(fixture: &Fixture) -> Element {
+        rsx! {
+            MosaicDragProvider {
+                DragSource { payload: DragPayload::Kind("editor".to_string()), "New editor" }
+                Mosaic { layout: fixture.layout, registry: fixture.registry.clone() }
+            }
+        }
+    }
+
+    #[test]
+    fn test_external_payloads_show_drop_zones() {
+        let mut mosaic = Harness::new(nested_layout(), palette);
+        let html = mosaic.html();
+        assert!(html.contains("class=\"mosaic-drag-source\""));
+        assert!(!html.contains("drop-zone-top"));
+
+        // Dragging from the palette shows the drop zones of every tile
+        let html = mosaic.hover(|drag| {
+            drag.start_payload_drag(DragPayload::Kind("editor".to_string()), 5.0, 5.0)
+        });
+        assert_eq!(html.matches("drop-zone drop-zone-top").count(), 3);
+        assert!(html.contains("class=\"drag-ghost\""));
+    }
+
+    /// A workspace and a strip of tiles sharing their drag state
+    fn two_mosaics(fixture: &Fixture) -> Element {
+        let strip = use_probed_layout(|| MosaicLayout::new("monitor".to_string()));
+        rsx! {
+            MosaicDragProvider {
+                Mosaic { layout: fixture.layout, registry: fixture.registry.clone() }
+                Mosaic { layout: strip, registry: fixture.registry.clone() }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tiles_move_between_mosaics() {
+        let mut mosaic = Harness::new(nested_layout(), two_mosaics);
+
+        // Only the mosaic the tile comes from draws the ghost
+        let html = mosaic.drag("terminal");
+        assert_eq!(html.matches("class=\"drag-ghost\"").count(), 1);
+        assert_eq!(html.matches("root-drop-zone root-drop-zone-top").count(), 2);
+
+        let strip = mosaic.layout_signal(1);
+        let source = mosaic.in_runtime(|| mosaic.drag_state().read().source);
+        assert!(source.is_some());
+        mosaic.in_scope(|| {
+            let payload = DragPayload::Tile("terminal".to_string());
+            assert!(drop_payload(
+                LayoutController::signal(strip, None),
+                None,
+                &payload,
+                source,
+                Some(&"monitor".to_string()),
+                DropZone::Bottom
+            ));
+        });
+        let html = mosaic.render();
+        assert_eq!(mosaic.layout().get_all_tiles(), ["sidebar", "editor"]);
+        assert_eq!(mosaic.layout_at(1).get_all_tiles(), ["monitor", "terminal"]);
+        assert!(html.contains("terminal 100x50"));
+    }
+
+    #[test]
+    fn test_empty_mosaics_take_drops() {
+        let mut mosaic = Harness::new(nested_layout(), two_mosaics);
+        let (workspace, strip) = (mosaic.layout_signal(0), mosaic.layout_signal(1));
+
+        // Move the last tile of the strip to the workspace
+        mosaic.in_scope(|| {
+            let payload = DragPayload::Tile("monitor".to_string());
+            assert!(drop_payload(
+                LayoutController::signal(workspace, None),
+                None,
+                &payload,
+                Some(LayoutController::signal(strip, None)),
+                None,
+                DropZone::Bottom
+            ));
+        });
+        assert!(mosaic.render().contains("No panels open"));
+
+        // Both mosaics show their edges while the tile is dragged back
+        let html = mosaic.drag("monitor");
+        assert_eq!(html.matches("root-drop-zone root-drop-zone-top").count(), 2);
+    }
+
+    fn drag_preview_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                registry: fixture.registry.clone(),
+                render_drag_preview: |preview: DragPreview| {
+                    let target = match preview.hover_target {
+                        Some((tile_id, zone)) => format!("{tile_id} {}", zone_name(zone)),
+                        None => "nothing".to_string(),
+                    };
+                    rsx! { "moving {preview.tile_id} over {target}" }
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_drag_preview() {
+        let mut mosaic = Harness::new(nested_layout(), drag_preview_mosaic);
+        let html = mosaic.drag("terminal");
+        assert!(html.contains("moving terminal over nothing"));
+        assert!(!html.contains("Dragging..."));
+
+        let html = mosaic.hover(|drag| drag.update_hover("editor".to_string(), DropZone::Left));
+        assert!(html.contains("moving terminal over editor left"));
+    }
+
+    fn snapshot_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                registry: fixture.registry.clone(),
+                drag_preview: DragPreviewOptions::default().offset(24.0, -40.0).snapshot(0.5),
+            }
+        }
+    }
+
+    #[test]
+    fn test_drag_preview_snapshot_and_offset() {
+        let html = Harness::new(nested_layout(), snapshot_mosaic).drag("terminal");
+        assert!(html.contains("left: 124px;") && html.contains("top: 60px;"));
+        let snapshot = &html[html.find("drag-ghost-snapshot").unwrap()..];
+        assert!(snapshot.contains("width: 200%; height: 200%; transform: scale(0.5);"));
+        // A copy of the tile's DOM, not a second instance of its components
+        assert_eq!(html.matches("terminal 75x30").count(), 1);
+    }
+
+    /// The terminal may only be dropped below other tiles
+    fn policy_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                registry: fixture.registry.clone(),
+                can_drop: |(tile_id, _target, zone): (TileId, Option<TileId>, DropZone)| {
+                    tile_id != "terminal" || zone == DropZone::Bottom
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_drop_hides_refused_zones() {
+        let html = Harness::new(nested_layout(), policy_mosaic).drag("terminal");
+
+        // Sidebar and editor only offer their bottom zone, and so do the edges
+        assert_eq!(
+            html.matches("class=\"drop-zone drop-zone-bottom\"").count(),
+            2
+        );
+        assert!(!html.contains("drop-zone drop-zone-top"));
+        assert!(html.contains("root-drop-zone root-drop-zone-bottom"));
+        assert!(!html.contains("root-drop-zone root-drop-zone-left"));
+    }
+
+    fn diagonal_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                registry: fixture.registry.clone(),
+                drop_zones: DropZoneGeometry::diagonal().with_center(0.5),
+            }
+        }
+    }
+
+    #[test]
+    fn test_drop_zone_geometry() {
+        let html = Harness::new(nested_layout(), diagonal_mosaic).drag("terminal");
+        assert_eq!(
+            html.matches("class=\"drop-zone drop-zone-center\"").count(),
+            2
+        );
+        assert!(html.contains("clip-path: polygon(0% 0%, 100% 0%, 75% 25%, 25% 25%);"));
+    }
+
+    fn unstyled_diagonal_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                registry: fixture.registry.clone(),
+                drop_zones: DropZoneGeometry::diagonal().with_center(0.5),
+                theme: MosaicTheme::unstyled(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unstyled_drop_zones_keep_their_geometry() {
+        let html = Harness::new(nested_layout(), unstyled_diagonal_mosaic).drag("terminal");
+        assert!(!html.contains("background-color"));
+        assert!(html.contains(
+            "class=\"drop-zone drop-zone-top\" style=\"position: absolute; left: 0; top: 0; width: 100%; height: 100%; clip-path: polygon(0% 0%, 100% 0%, 75% 25%, 25% 25%);\""
+        ));
+        assert!(html.contains(
+            "class=\"drop-zone drop-zone-center\" style=\"position: absolute; left: 25%;"
+        ));
+    }
+
+    fn live_preview_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic { layout: fixture.layout, registry: fixture.registry.clone(), live_preview: true }
+        }
+    }
+
+    /// Style of the dragged tile in the layout preview
+    fn dropped_preview_style(html: &str) -> &str {
+        let dropped = &html[html.find("mosaic-layout-preview-tile dropped").unwrap()..];
+        &dropped[..dropped.find("\">").unwrap()]
+    }
+
+    #[test]
+    fn test_live_preview_shows_resulting_layout() {
+        let mut mosaic = Harness::new(nested_layout(), live_preview_mosaic);
+        let html = mosaic.drag("terminal");
+        assert!(!html.contains("mosaic-layout-preview"));
+
+        // Dropping on the left of the editor would give the terminal half of its width
+        let html = mosaic.hover(|drag| drag.update_hover("editor".to_string(), DropZone::Left));
+        assert_eq!(
+            html.matches("class=\"mosaic-layout-preview-tile").count(),
+            3
+        );
+        let style = dropped_preview_style(&html);
+        assert!(style.contains("width: calc(37.5% - 0.5 * "));
+        assert!(style.contains("height: 100%;"));
+
+        // Docking at the bottom edge would give it the full width
+        let html = mosaic.hover(|drag| drag.hover_edge(DropZone::Bottom));
+        let style = dropped_preview_style(&html);
+        assert!(style.contains("top: calc(70% + 1 * "));
+        assert!(style.contains("width: 100%;"));
+
+        // Inserting it on the root divider would put it between sidebar and editor
+        let root = mosaic.layout().root().cloned().unwrap();
+        let html = mosaic.hover(|drag| drag.hover_divider(root));
+        let style = dropped_preview_style(&html);
+        assert!(style.contains("top: 0%;") && style.contains("height: 100%;"));
+        assert!(!style.contains("left: 0%;"));
+
+        // The layout itself is left untouched
+        assert_eq!(mosaic.layout(), nested_layout());
+    }
+
+    #[test]
+    fn test_drag_split_as_a_group() {
+        let mut mosaic = Harness::new(nested_layout(), live_preview_mosaic);
+        // Only the editor/terminal split has a grab handle, the root has nowhere to go
+        assert_eq!(
+            mosaic
+                .html()
+                .matches("class=\"mosaic-split-handle\"")
+                .count(),
+            1
+        );
+
+        let controller = mosaic.controller();
+        let layout = mosaic.layout();
+        let inner = layout
+            .find_tile(&"editor".to_string())
+            .and_then(|id| layout.get_node(&id)?.parent().cloned())
+            .unwrap();
+        let html = mosaic.hover(|drag| drag.start_split_drag(controller, inner, 100.0, 100.0));
+        // Both tiles of the split are dragged, and only the sidebar takes the drop
+        assert_eq!(html.matches("mosaic-tile-pane dragging").count(), 2);
+        assert_eq!(html.matches("drop-zone drop-zone-top").count(), 1);
+        assert!(html.contains("editor</span></span><span> + <span>terminal"));
+
+        let html = mosaic.hover(|drag| drag.update_hover("sidebar".to_string(), DropZone::Right));
+        assert_eq!(
+            html.matches("mosaic-layout-preview-tile dropped").count(),
+            2
+        );
+    }
+
+    /// sidebar | (editor / terminal), with a pinned sidebar
+    fn pinned_sidebar_layout() -> MosaicLayout {
+        let mut layout = nested_layout();
+        let pinned = TileCapabilities::locked().draggable(false).resizable(false);
+        layout.set_tile_capabilities(&"sidebar".to_string(), pinned);
+        layout
+    }
+
+    #[test]
+    fn test_tile_capabilities_in_the_ui() {
+        let mut mosaic = Harness::new(pinned_sidebar_layout(), plain_mosaic);
+        let html = mosaic.html();
+        // No close button nor drag on the sidebar, and its divider doesn't move
+        assert_eq!(html.matches("class=\"mosaic-tile-close\"").count(), 2);
+        assert_eq!(
+            html.matches("class=\"mosaic-tile-header\" draggable=\"false\"")
+                .count(),
+            1
+        );
+        assert!(html.contains("cursor: default;"));
+        assert!(html.contains("cursor: row-resize;"));
+
+        let sidebar = mosaic.handle("sidebar");
+        assert!(sidebar.is_locked());
+        assert_eq!(
+            sidebar
+                .capabilities()
+                .map(|capabilities| capabilities.draggable),
+            Some(false)
+        );
+        mosaic.update(|| sidebar.start_drag(10.0, 10.0));
+        assert!(mosaic.in_runtime(|| !mosaic.drag_state().read().is_dragging()));
+
+        // Other tiles are dragged, but aren't dropped on the sidebar
+        let html = mosaic.drag("terminal");
+        // Tiles are rendered in ID order: editor, sidebar, terminal
+        let sidebar_html =
+            &html[html.find("sidebar 25x100").unwrap()..html.find("terminal 75x30").unwrap()];
+        assert!(!sidebar_html.contains("drop-zone drop-zone-"));
+        assert_eq!(html.matches("drop-zone drop-zone-top").count(), 1);
+    }
+
+    fn moded_mosaic(fixture: &Fixture) -> Element {
+        rsx! {
+            Mosaic {
+                layout: fixture.layout,
+                registry: fixture.registry.clone(),
+                mode: (fixture.mode)(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_only_mode() {
+        let mut mosaic = Harness::mount(HarnessProps {
+            layout: nested_layout(),
+            mode: MosaicMode::ReadOnly,
+            mosaic: moded_mosaic,
+        });
+        let html = mosaic.html();
+        assert!(html.contains("mosaic-container read-only"));
+        assert!(!html.contains("mosaic-tile-close"));
+        assert!(!html.contains("mosaic-split-handle"));
+        assert_eq!(
+            html.matches("class=\"mosaic-tile-header\" draggable=\"false\"")
+                .count(),
+            3
+        );
+        assert!(!html.contains("cursor: row-resize;"));
+        assert!(!html.contains("cursor: col-resize;"));
+
+        // Handles can't change anything, and nothing can be dropped
+        let editor = mosaic.handle("editor");
+        assert_eq!(editor.capabilities(), Some(TileCapabilities::none()));
+        assert!(mosaic.in_runtime(|| !editor.split(SplitDirection::Horizontal, "logs")));
+        mosaic.in_runtime(|| editor.close());
+        let payload = DragPayload::Tile("terminal".to_string());
+        assert!(!mosaic.controller().can_drop(
+            &payload,
+            Some(&"editor".to_string()),
+            DropZone::Left
+        ));
+        let html = mosaic.drag("terminal");
+        assert!(!html.contains("drop-zone drop-zone-"));
+
+        // Programmatic changes are still reflected
+        let mut layout = mosaic.layout_signal(0);
+        let html = mosaic.update(|| {
+            layout.write().close_tile(&"terminal".to_string());
+        });
+        assert!(html.contains("editor 75x100"));
+        assert!(!html.contains("terminal 75x30"));
+
+        // And editing comes back with the mode, in a single render
+        let mut mode = mosaic.mode();
+        let html = mosaic.update(|| mode.set(MosaicMode::Edit));
+        assert!(!html.contains("read-only"));
+        assert_eq!(html.matches("class=\"mosaic-tile-close\"").count(), 2);
+        assert!(html.contains("cursor: col-resize;"));
+        assert!(mosaic
+            .handle("editor")
+            .capabilities()
+            .is_some_and(|capabilities| capabilities.closable));
+
+        let html = mosaic.update(|| mode.set(MosaicMode::ReadOnly));
+        assert!(html.contains("mosaic-container read-only"));
+        assert!(!html.contains("cursor: col-resize;"));
     }
 }
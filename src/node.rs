@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Each node is either a Split (containing two child nodes) or a Tile (leaf node with content).
 /// Nodes are stored in a HashMap for O(1) access.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Node {
     /// A split node containing two children
     Split {
@@ -50,11 +50,103 @@ pub enum Node {
         parent: Option<NodeId>,
 
         // Metadata
-        /// Whether this tile is locked (prevents closing)
-        locked: bool,
+        /// What users can do with this tile (close it, drag it, drop on it...)
+        capabilities: TileCapabilities,
     },
 }
 
+/// What users can do with a tile
+///
+/// Every capability is enabled by default. They are enforced by the
+/// `MosaicLayout` operations and reflected in the UI (e.g. no close button).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TileCapabilities {
+    /// The tile can be closed
+    pub closable: bool,
+    /// The tile can be dragged elsewhere, alone or with a split it is part of
+    pub draggable: bool,
+    /// Other tiles can be dropped on the tile
+    pub drop_target: bool,
+    /// The dividers along the tile can be moved
+    pub resizable: bool,
+    /// The tile can be split to open another one next to it
+    pub splittable: bool,
+}
+
+impl Default for TileCapabilities {
+    fn default() -> Self {
+        Self {
+            closable: true,
+            draggable: true,
+            drop_target: true,
+            resizable: true,
+            splittable: true,
+        }
+    }
+}
+
+impl TileCapabilities {
+    /// No capabilities at all, e.g. for the tiles of a read-only mosaic
+    pub fn none() -> Self {
+        Self {
+            closable: false,
+            draggable: false,
+            drop_target: false,
+            resizable: false,
+            splittable: false,
+        }
+    }
+
+    /// Capabilities of a locked tile: it can't be closed nor be dropped on
+    pub fn locked() -> Self {
+        Self::default().closable(false).drop_target(false)
+    }
+
+    /// Whether the tile is locked, i.e. can neither be closed nor be dropped on
+    pub fn is_locked(&self) -> bool {
+        !self.closable && !self.drop_target
+    }
+
+    /// Lock or unlock the tile (see `locked`), keeping the other capabilities
+    ///
+    /// `is_locked` returns `locked` afterwards.
+    pub fn set_locked(&mut self, locked: bool) {
+        self.closable = !locked;
+        self.drop_target = !locked;
+    }
+
+    /// Allow or forbid closing the tile
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Allow or forbid dragging the tile
+    pub fn draggable(mut self, draggable: bool) -> Self {
+        self.draggable = draggable;
+        self
+    }
+
+    /// Allow or forbid dropping other tiles on the tile
+    pub fn drop_target(mut self, drop_target: bool) -> Self {
+        self.drop_target = drop_target;
+        self
+    }
+
+    /// Allow or forbid moving the dividers along the tile
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Allow or forbid splitting the tile
+    pub fn splittable(mut self, splittable: bool) -> Self {
+        self.splittable = splittable;
+        self
+    }
+}
+
 impl Node {
     /// Get the node's ID
     pub fn id(&self) -> &NodeId {
@@ -90,6 +182,23 @@ impl Node {
         matches!(self, Node::Tile { .. })
     }
 
+    /// Whether the node is locked: a locked split can't be resized, a locked
+    /// tile can neither be closed nor be dropped on (see `TileCapabilities::is_locked`)
+    pub fn is_locked(&self) -> bool {
+        match self {
+            Node::Split { locked, .. } => *locked,
+            Node::Tile { capabilities, .. } => capabilities.is_locked(),
+        }
+    }
+
+    /// Capabilities of the tile (for Tile nodes only)
+    pub fn capabilities(&self) -> Option<TileCapabilities> {
+        match self {
+            Node::Tile { capabilities, .. } => Some(*capabilities),
+            Node::Split { .. } => None,
+        }
+    }
+
     /// Get child node IDs (for Split nodes only)
     pub fn children(&self) -> Option<(&NodeId, &NodeId)> {
         match self {
@@ -0,0 +1,354 @@
+use crate::format::LoadError;
+use crate::layout::MosaicLayout;
+use crate::storage::LayoutStorage;
+#[cfg(feature = "web")]
+use crate::storage::LocalStorage;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Quiet period after the last layout change before it is written to storage
+const SAVE_DEBOUNCE_MS: u32 = 400;
+
+/// Number of dividers currently being dragged
+///
+/// Provided by `use_persistent_layout` and updated by `SplitPane`, so that
+/// saves are held back until a resize gesture is finished.
+#[derive(Clone, Copy)]
+pub(crate) struct ActiveResizes {
+    active: Signal<usize>,
+    /// Bumped for every queued save, only the latest one is written
+    generation: CopyValue<u64>,
+}
+
+impl ActiveResizes {
+    pub(crate) fn begin(mut self) {
+        // A save queued just before the drag must not land in the middle of it
+        *self.generation.write() += 1;
+        *self.active.write() += 1;
+    }
+
+    pub(crate) fn end(mut self) {
+        let mut active = self.active.write();
+        *active = active.saturating_sub(1);
+    }
+
+    fn is_active(&self) -> bool {
+        (self.active)() > 0
+    }
+
+    /// Generation of a save queued now
+    fn queue_save(mut self) -> u64 {
+        let mut generation = self.generation.write();
+        *generation += 1;
+        *generation
+    }
+
+    /// Whether no save was queued and no resize started since `generation`
+    fn is_latest(&self, generation: u64) -> bool {
+        *self.generation.peek() == generation
+    }
+}
+
+/// Handle returned by [`use_persistent_layout`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct PersistentLayout {
+    layout: Signal<MosaicLayout>,
+    load_error: Signal<Option<LoadError>>,
+    default_fn: Callback<(), MosaicLayout>,
+}
+
+impl PersistentLayout {
+    /// The layout signal, to be passed to `Mosaic`
+    pub fn layout(&self) -> Signal<MosaicLayout> {
+        self.layout
+    }
+
+    /// Why the stored layout could not be restored on mount, if it failed
+    ///
+    /// `LoadError::NotFound` is not reported since a first visit is expected.
+    pub fn load_error(&self) -> Option<LoadError> {
+        (self.load_error)()
+    }
+
+    /// Replace the current layout with the default one
+    ///
+    /// The default layout is then saved like any other change.
+    pub fn reset(&mut self) {
+        let default = self.default_fn.call(());
+        self.layout.set(default);
+    }
+}
+
+/// Layout signal restored from localStorage and saved back on every change
+///
+/// Requires the `web` feature. Shorthand for
+/// `use_persistent_layout_with(LocalStorage, key, default_fn)`.
+///
+/// # Example
+/// ```ignore
+/// let workspace = use_persistent_layout("my-app-layout", || {
+///     MosaicBuilder::horizontal()
+///         .left(tile("sidebar"))
+///         .right(tile("editor"))
+///         .split(25.0)
+///         .build()
+/// });
+///
+/// rsx! {
+///     Mosaic { layout: workspace.layout(), /* ... */ }
+/// }
+/// ```
+#[cfg(feature = "web")]
+pub fn use_persistent_layout(
+    key: impl Into<String>,
+    default_fn: impl Fn() -> MosaicLayout + 'static,
+) -> PersistentLayout {
+    use_persistent_layout_with(LocalStorage, key, default_fn)
+}
+
+/// Layout signal restored from `storage` and saved back on every change
+///
+/// On mount the layout stored under `key` is loaded, falling back to
+/// `default_fn` when nothing usable is stored. Changes are written back after
+/// a short debounce, and never while a divider is being dragged.
+pub fn use_persistent_layout_with<S: LayoutStorage + 'static>(
+    storage: S,
+    key: impl Into<String>,
+    default_fn: impl Fn() -> MosaicLayout + 'static,
+) -> PersistentLayout {
+    let storage = use_hook(|| Rc::new(storage) as Rc<dyn LayoutStorage>);
+    let key = use_hook(|| Rc::new(key.into()));
+    let default_fn = use_callback(move |()| default_fn());
+
+    // Load once, then seed both signals with the outcome
+    let (layout, load_error) = use_hook(|| {
+        let (layout, error) = match MosaicLayout::load_from(storage.as_ref(), &key) {
+            Ok(layout) => (layout, None),
+            Err(LoadError::NotFound) => (default_fn.call(()), None),
+            Err(err) => {
+                dioxus::logger::tracing::warn!("Could not restore layout {:?}: {}", key, err);
+                (default_fn.call(()), Some(err))
+            }
+        };
+        (Signal::new(layout), Signal::new(error))
+    });
+
+    let resizes = use_context_provider(|| ActiveResizes {
+        active: Signal::new(0),
+        generation: CopyValue::new(0),
+    });
+    let mut first_run = use_hook(|| CopyValue::new(true));
+
+    use_effect(move || {
+        let snapshot = layout.read().clone();
+        if resizes.is_active() {
+            return;
+        }
+
+        // The initial layout was just loaded (or defaulted), nothing to save yet
+        if *first_run.peek() {
+            first_run.set(false);
+            return;
+        }
+
+        let current = resizes.queue_save();
+        let storage = storage.clone();
+        let key = key.clone();
+        spawn(async move {
+            sleep_ms(SAVE_DEBOUNCE_MS).await;
+            if !resizes.is_latest(current) {
+                return;
+            }
+            if let Err(err) = snapshot.save_to(storage.as_ref(), &key) {
+                dioxus::logger::tracing::warn!("Could not save layout {:?}: {}", key, err);
+            }
+        });
+    });
+
+    PersistentLayout {
+        layout,
+        load_error,
+        default_fn,
+    }
+}
+
+/// Wait for `ms` milliseconds on whichever executor the renderer uses
+async fn sleep_ms(ms: u32) {
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+
+    // Desktop and liveview renderers run on tokio
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(ms))).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{MemoryStorage, StorageError};
+    use crate::types::SplitDirection;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// `MemoryStorage` counting the writes it receives
+    #[derive(Clone, Default)]
+    struct CountingStorage {
+        entries: MemoryStorage,
+        writes: Arc<AtomicUsize>,
+    }
+
+    impl CountingStorage {
+        fn writes(&self) -> usize {
+            self.writes.load(Ordering::SeqCst)
+        }
+
+        fn stored_tiles(&self) -> Vec<String> {
+            let mut tiles = MosaicLayout::load_from(self, "layout")
+                .unwrap()
+                .get_all_tiles();
+            tiles.sort();
+            tiles
+        }
+    }
+
+    impl LayoutStorage for CountingStorage {
+        fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+            self.entries.read(key)
+        }
+
+        fn write(&self, key: &str, value: &str) -> Result<(), StorageError> {
+            self.writes.fetch_add(1, Ordering::SeqCst);
+            self.entries.write(key, value)
+        }
+
+        fn remove(&self, key: &str) -> Result<(), StorageError> {
+            self.entries.remove(key)
+        }
+    }
+
+    thread_local! {
+        /// Handle returned to `persistent_app`
+        static WORKSPACE: std::cell::Cell<Option<PersistentLayout>> = const { std::cell::Cell::new(None) };
+    }
+
+    fn persistent_app(storage: CountingStorage) -> Element {
+        let workspace = use_persistent_layout_with(storage, "layout", || {
+            MosaicLayout::new("editor".to_string())
+        });
+        WORKSPACE.set(Some(workspace));
+        rsx! {}
+    }
+
+    fn mount(storage: &CountingStorage) -> VirtualDom {
+        let mut dom = VirtualDom::new_with_props(persistent_app, storage.clone());
+        dom.rebuild_in_place();
+        dom
+    }
+
+    /// Run the app for `ms` milliseconds of (paused) tokio time
+    async fn run_for(dom: &mut VirtualDom, ms: u64) {
+        let _ = tokio::time::timeout(Duration::from_millis(ms), async {
+            loop {
+                dom.wait_for_work().await;
+                dom.render_immediate_to_vec();
+            }
+        })
+        .await;
+    }
+
+    fn split_editor(dom: &VirtualDom, new_tile_id: &str) {
+        let mut layout = WORKSPACE.get().unwrap().layout();
+        dom.in_runtime(|| {
+            layout.write().split_tile(
+                &"editor".to_string(),
+                SplitDirection::Horizontal,
+                new_tile_id.to_string(),
+                50.0,
+            );
+        });
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_saves_are_debounced() {
+        let storage = CountingStorage::default();
+        let mut dom = mount(&storage);
+        run_for(&mut dom, 1000).await;
+        // Nothing to save right after mounting
+        assert_eq!(storage.writes(), 0);
+
+        split_editor(&dom, "terminal");
+        run_for(&mut dom, 100).await;
+        split_editor(&dom, "sidebar");
+        run_for(&mut dom, 100).await;
+        assert_eq!(storage.writes(), 0);
+
+        run_for(&mut dom, 1000).await;
+        assert_eq!(storage.writes(), 1);
+        assert_eq!(storage.stored_tiles(), ["editor", "sidebar", "terminal"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_no_save_while_resizing() {
+        let storage = CountingStorage::default();
+        let mut dom = mount(&storage);
+        run_for(&mut dom, 1000).await;
+        let resizes = dom.in_scope(ScopeId::APP, consume_context::<ActiveResizes>);
+
+        // A save queued just before the drag is dropped, and so are changes during it
+        split_editor(&dom, "terminal");
+        run_for(&mut dom, 100).await;
+        dom.in_runtime(|| resizes.begin());
+        split_editor(&dom, "sidebar");
+        run_for(&mut dom, 1000).await;
+        assert_eq!(storage.writes(), 0);
+
+        // The layout is saved once the resize is over
+        dom.in_runtime(|| resizes.end());
+        run_for(&mut dom, 1000).await;
+        assert_eq!(storage.writes(), 1);
+        assert_eq!(storage.stored_tiles(), ["editor", "sidebar", "terminal"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reset_saves_the_default_layout() {
+        let storage = CountingStorage::default();
+        let mut stored = MosaicLayout::new("editor".to_string());
+        stored.split_tile(
+            &"editor".to_string(),
+            SplitDirection::Vertical,
+            "terminal".to_string(),
+            70.0,
+        );
+        stored.save_to(&storage.entries, "layout").unwrap();
+
+        let mut dom = mount(&storage);
+        run_for(&mut dom, 1000).await;
+        let mut workspace = WORKSPACE.get().unwrap();
+        assert_eq!(workspace.load_error(), None);
+        assert_eq!(workspace.layout().peek().get_all_tiles().len(), 2);
+
+        dom.in_runtime(|| workspace.reset());
+        run_for(&mut dom, 1000).await;
+        assert_eq!(workspace.layout().peek().get_all_tiles(), ["editor"]);
+        assert_eq!(storage.writes(), 1);
+        assert_eq!(storage.stored_tiles(), ["editor"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unreadable_layout_falls_back_to_the_default() {
+        let storage = CountingStorage::default();
+        storage.entries.write("layout", "not a layout").unwrap();
+
+        let mut dom = mount(&storage);
+        run_for(&mut dom, 1000).await;
+        let workspace = WORKSPACE.get().unwrap();
+        assert!(matches!(
+            workspace.load_error(),
+            Some(LoadError::Malformed(_))
+        ));
+        assert_eq!(workspace.layout().peek().get_all_tiles(), ["editor"]);
+        // The stored payload is left alone until the layout changes
+        assert_eq!(storage.writes(), 0);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::layout::MosaicLayout;
+use crate::types::TileId;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Renderer and metadata for one kind of tile
+///
+/// # Example
+/// ```ignore
+/// TileKind::new(|tile_id| rsx! { Editor { file: tile_id } })
+///     .title("Editor")
+///     .icon("📝")
+///     .default_size(60.0)
+/// ```
+#[derive(Clone)]
+pub struct TileKind {
+    render: Rc<dyn Fn(TileId) -> Element>,
+    title: Option<String>,
+    render_title: Option<Rc<dyn Fn(TileId) -> Element>>,
+    icon: Option<String>,
+    default_size: Option<f64>,
+    allow_multiple: bool,
+}
+
+impl TileKind {
+    /// Create a kind rendered by `render`, which receives the full tile ID
+    pub fn new(render: impl Fn(TileId) -> Element + 'static) -> Self {
+        Self {
+            render: Rc::new(render),
+            title: None,
+            render_title: None,
+            icon: None,
+            default_size: None,
+            allow_multiple: true,
+        }
+    }
+
+    /// Static title shown in the tile header (defaults to the tile ID)
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Render the title from the tile ID, taking precedence over `title`
+    pub fn render_title(mut self, render_title: impl Fn(TileId) -> Element + 'static) -> Self {
+        self.render_title = Some(Rc::new(render_title));
+        self
+    }
+
+    /// Icon shown before the title (an emoji or icon-font glyph)
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Preferred share of a split for new tiles of this kind (percentage)
+    pub fn default_size(mut self, percentage: f64) -> Self {
+        self.default_size = Some(percentage);
+        self
+    }
+
+    /// Whether several tiles of this kind can be open at once (default: true)
+    pub fn allow_multiple(mut self, allow: bool) -> Self {
+        self.allow_multiple = allow;
+        self
+    }
+}
+
+#[derive(Clone, Default)]
+struct RegistryInner {
+    kinds: HashMap<String, TileKind>,
+    fallback: Option<Rc<dyn Fn(TileId) -> Element>>,
+}
+
+/// Tile kinds known to a `Mosaic`, replacing `render_tile` / `render_title`
+///
+/// A tile's kind is the part of its ID before the first `:`, so `"editor:main.rs"`
+/// and `"editor:lib.rs"` are both rendered by the `"editor"` kind, while
+/// `"sidebar"` is its own kind. Tiles of an unknown kind go to the fallback.
+///
+/// # Example
+/// ```ignore
+/// let registry = TileRegistry::new()
+///     .register("sidebar", TileKind::new(|_| rsx! { Sidebar {} }).title("Files").allow_multiple(false))
+///     .register("editor", TileKind::new(|tile_id| rsx! { Editor { tile_id } }).icon("📝"))
+///     .fallback(|tile_id| rsx! { "Unknown tile {tile_id}" });
+///
+/// rsx! { Mosaic { layout, registry } }
+/// ```
+#[derive(Clone, Default)]
+pub struct TileRegistry {
+    inner: Rc<RegistryInner>,
+}
+
+impl PartialEq for TileRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl TileRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) the renderer for a kind of tile
+    pub fn register(mut self, kind: impl Into<String>, tile_kind: TileKind) -> Self {
+        Rc::make_mut(&mut self.inner)
+            .kinds
+            .insert(kind.into(), tile_kind);
+        self
+    }
+
+    /// Renderer for tiles whose kind isn't registered
+    pub fn fallback(mut self, render: impl Fn(TileId) -> Element + 'static) -> Self {
+        Rc::make_mut(&mut self.inner).fallback = Some(Rc::new(render));
+        self
+    }
+
+    /// Kind of a tile: its ID up to the first `:`
+    pub fn kind_of(tile_id: &str) -> &str {
+        tile_id.split(':').next().unwrap_or(tile_id)
+    }
+
+    /// The registered kind of a tile, if any
+    pub fn get(&self, tile_id: &str) -> Option<&TileKind> {
+        self.inner.kinds.get(Self::kind_of(tile_id))
+    }
+
+    /// Render a tile's content, using the fallback for unknown kinds
+    pub fn render(&self, tile_id: TileId) -> Element {
+        self.try_render(tile_id.clone()).unwrap_or_else(|| {
+            rsx! {
+                div { class: "mosaic-unknown-tile", "Unknown tile: {tile_id}" }
+            }
+        })
+    }
+
+    /// Render a tile's content, or `None` if its kind is unknown and there's no fallback
+    pub fn try_render(&self, tile_id: TileId) -> Option<Element> {
+        if let Some(kind) = self.get(&tile_id) {
+            return Some((kind.render)(tile_id));
+        }
+        self.inner
+            .fallback
+            .as_ref()
+            .map(|fallback| fallback(tile_id))
+    }
+
+    /// Render a tile's title, preceded by its icon
+    pub fn render_title(&self, tile_id: TileId) -> Element {
+        let Some(kind) = self.get(&tile_id) else {
+            return rsx! { span { "{tile_id}" } };
+        };
+        let title = match (&kind.render_title, &kind.title) {
+            (Some(render_title), _) => render_title(tile_id),
+            (None, Some(title)) => rsx! { span { "{title}" } },
+            (None, None) => rsx! { span { "{tile_id}" } },
+        };
+        rsx! {
+            if let Some(icon) = &kind.icon {
+                span { class: "mosaic-tile-icon", "{icon} " }
+            }
+            {title}
+        }
+    }
+
+    /// Preferred split share for a new tile, from its kind
+    pub fn size_hint(&self, tile_id: &str) -> Option<f64> {
+        self.get(tile_id).and_then(|kind| kind.default_size)
+    }
+
+    /// Whether `tile_id` can be added to `layout`
+    ///
+    /// A tile can't be added twice, and kinds that don't allow multiple
+    /// instances can only have one tile in the layout.
+    pub fn can_open(&self, layout: &MosaicLayout, tile_id: &str) -> bool {
+        let open = layout.get_all_tiles();
+        if open.iter().any(|open_id| open_id == tile_id) {
+            return false;
+        }
+        match self.get(tile_id) {
+            Some(kind) if !kind.allow_multiple => {
+                let kind = Self::kind_of(tile_id);
+                !open.iter().any(|open_id| Self::kind_of(open_id) == kind)
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> TileRegistry {
+        TileRegistry::new()
+            .register(
+                "sidebar",
+                TileKind::new(|_| rsx! { "files" }).allow_multiple(false),
+            )
+            .register(
+                "editor",
+                TileKind::new(|tile_id| rsx! { "{tile_id}" }).default_size(60.0),
+            )
+    }
+
+    #[test]
+    fn test_kind_of() {
+        assert_eq!(TileRegistry::kind_of("editor:main.rs"), "editor");
+        assert_eq!(TileRegistry::kind_of("editor:src/a:b.rs"), "editor");
+        assert_eq!(TileRegistry::kind_of("sidebar"), "sidebar");
+    }
+
+    #[test]
+    fn test_lookup_by_kind() {
+        let registry = registry();
+        assert!(registry.get("editor:main.rs").is_some());
+        assert!(registry.get("terminal").is_none());
+        assert_eq!(registry.size_hint("editor:lib.rs"), Some(60.0));
+        assert_eq!(registry.size_hint("sidebar"), None);
+    }
+
+    #[test]
+    fn test_can_open_respects_single_instance_kinds() {
+        let registry = registry();
+        let layout = MosaicLayout::new("sidebar:left".to_string());
+
+        assert!(!registry.can_open(&layout, "sidebar:left"));
+        assert!(!registry.can_open(&layout, "sidebar:right"));
+        assert!(registry.can_open(&layout, "editor:main.rs"));
+        assert!(registry.can_open(&layout, "unknown"));
+    }
+}
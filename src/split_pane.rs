@@ -1,11 +1,26 @@
+use crate::persistent_layout::ActiveResizes;
+use crate::theme::use_theme_style;
 use crate::types::SplitDirection;
+use dioxus::html::geometry::PixelsRect;
 use dioxus::prelude::*;
-use wasm_bindgen::JsCast;
+use std::rc::Rc;
 
 /// A resizable split pane component
 ///
 /// Allows users to drag a divider to resize two child panels.
 /// Supports both horizontal (left/right) and vertical (top/bottom) splits.
+///
+/// The split is rendered from `initial_size` unless a drag is in progress, so the
+/// markup only depends on props (stable for server-side rendering and hydration).
+/// When `on_resize` is set, the parent owns the position and is expected to feed
+/// the new value back; without it the pane keeps the dragged position itself.
+/// `on_resizing` reports the position while the divider is dragged, e.g. to
+/// move content laid out outside of the panes along with it.
+///
+/// With `on_divider_drop`, the divider and its gaps accept HTML drag-and-drop
+/// drops (e.g. a dragged tile to insert between the panes).
+/// `on_divider_hover` reports when a drag enters (`true`) or leaves (`false`)
+/// them, e.g. to preview the drop.
 #[component]
 pub fn SplitPane(
     direction: SplitDirection,
@@ -15,12 +30,57 @@ pub fn SplitPane(
     on_resize: Option<EventHandler<f64>>, // Called when user finishes dragging
     first_pane: Element,
     second_pane: Element,
+    /// Called with the position while the divider is dragged
+    #[props(default)]
+    on_resizing: Option<EventHandler<f64>>,
+    /// Called when something is dropped on the divider, making it a drop target
+    #[props(default)]
+    on_divider_drop: Option<EventHandler<()>>,
+    /// Called when a drag enters (`true`) or leaves (`false`) the divider drop target
+    #[props(default)]
+    on_divider_hover: Option<EventHandler<bool>>,
+    /// Whether the divider can be dragged
+    #[props(default = true)]
+    resizable: bool,
 ) -> Element {
-    // State for current split position (percentage)
-    let mut split_pos = use_signal(|| initial_size);
+    // Position chosen by dragging that the parent hasn't taken over yet (percentage)
+    let mut dragged_pos = use_signal(|| None::<f64>);
+    let split_pos = move || dragged_pos().unwrap_or(initial_size);
     let mut is_dragging = use_signal(|| false);
     let mut is_hovering = use_signal(|| false);
-    let mut container_ref = use_signal(|| None::<web_sys::HtmlElement>);
+    let mut is_drop_target = use_signal(|| false);
+    let mut container_ref = use_signal(|| None::<Rc<MountedData>>);
+
+    // Container bounds, measured when a drag starts (works on every renderer)
+    let mut container_rect = use_signal(|| None::<PixelsRect>);
+
+    // Let a persistent layout hold back saves while the divider is dragged
+    let resizes = try_use_context::<ActiveResizes>();
+    let mut start_drag = move || {
+        if resizable && !is_dragging() {
+            is_dragging.set(true);
+            if let Some(resizes) = resizes {
+                resizes.begin();
+            }
+            if let Some(container) = container_ref() {
+                spawn(async move {
+                    if let Ok(rect) = container.get_client_rect().await {
+                        container_rect.set(Some(rect));
+                    }
+                });
+            }
+        }
+    };
+
+    // A divider unmounted mid-drag (tile closed, layout replaced) never gets
+    // its mouseup, so the persistent layout would stay paused
+    use_drop(move || {
+        if let Some(resizes) = resizes {
+            if is_dragging.try_peek().is_ok_and(|dragging| *dragging) {
+                resizes.end();
+            }
+        }
+    });
 
     // Mouse move handler for dragging
     let handle_mouse_move = move |evt: Event<MouseData>| {
@@ -28,25 +88,26 @@ pub fn SplitPane(
             return;
         }
 
-        if let Some(container) = container_ref() {
-            let rect = container.get_bounding_client_rect();
-
+        if let Some(rect) = container_rect() {
             let new_pos = match direction {
                 SplitDirection::Horizontal => {
-                    let x = evt.page_coordinates().x as f64;
-                    let container_x = rect.left();
-                    let container_width = rect.width();
+                    let x = evt.client_coordinates().x;
+                    let container_x = rect.origin.x;
+                    let container_width = rect.size.width;
                     ((x - container_x) / container_width * 100.0).clamp(min_size, max_size)
                 }
                 SplitDirection::Vertical => {
-                    let y = evt.page_coordinates().y as f64;
-                    let container_y = rect.top();
-                    let container_height = rect.height();
+                    let y = evt.client_coordinates().y;
+                    let container_y = rect.origin.y;
+                    let container_height = rect.size.height;
                     ((y - container_y) / container_height * 100.0).clamp(min_size, max_size)
                 }
             };
 
-            split_pos.set(new_pos);
+            dragged_pos.set(Some(new_pos));
+            if let Some(handler) = &on_resizing {
+                handler.call(new_pos);
+            }
         }
     };
 
@@ -55,164 +116,371 @@ pub fn SplitPane(
         if is_dragging() {
             let current_pos = split_pos();
             is_dragging.set(false);
-            // Notify parent of new position
+            // Notify parent of new position, from now on it comes back through the props
             if let Some(handler) = &on_resize {
                 handler.call(current_pos);
+                dragged_pos.set(None);
+            }
+            if let Some(resizes) = resizes {
+                resizes.end();
             }
         }
     };
 
-    let cursor = match direction {
-        SplitDirection::Horizontal => "col-resize",
-        SplitDirection::Vertical => "row-resize",
+    let cursor = match (resizable, direction) {
+        (false, _) => "default",
+        (true, SplitDirection::Horizontal) => "col-resize",
+        (true, SplitDirection::Vertical) => "row-resize",
+    };
+    let theme = use_theme_style();
+    let styled = theme.styled;
+
+    // The divider and its gaps always take pointer events, even when placed over
+    // other content by a parent ignoring them (like `Mosaic`'s split frames)
+    let gap_style = if styled {
+        format!("pointer-events: auto; background-color: transparent; cursor: {cursor};")
+    } else {
+        "pointer-events: auto;".to_string()
+    };
+
+    let divider_style = if styled {
+        let bg_color = if is_drop_target() {
+            "var(--mosaic-drop-zone-active-border)"
+        } else if is_hovering() {
+            "var(--mosaic-divider-hover)"
+        } else {
+            "var(--mosaic-divider)"
+        };
+        format!(
+            "
+            pointer-events: auto;
+            background-color: {bg_color};
+            cursor: {cursor};
+            transition: background-color 0.2s ease;
+            position: relative;
+            border-radius: var(--mosaic-control-radius);
+        "
+        )
+    } else {
+        "pointer-events: auto;".to_string()
+    };
+
+    // Grip line drawn in the middle of the divider
+    let indicator_style = if styled {
+        let indicator_color = if is_hovering() {
+            "var(--mosaic-divider-indicator-hover)"
+        } else {
+            "var(--mosaic-divider-indicator)"
+        };
+        let (width, height) = match direction {
+            SplitDirection::Horizontal => ("2px", "30px"),
+            SplitDirection::Vertical => ("30px", "2px"),
+        };
+        format!(
+            "
+            position: absolute;
+            top: 50%;
+            left: 50%;
+            transform: translate(-50%, -50%);
+            width: {width};
+            height: {height};
+            background-color: {indicator_color};
+            border-radius: 2px;
+            transition: background-color 0.2s ease;
+        "
+        )
+    } else {
+        String::new()
+    };
+
+    // Drops on the divider or its gaps, while a drop is possible
+    let accepts_drop = on_divider_drop.is_some();
+    let handle_drag_over = move |evt: Event<DragData>| {
+        if accepts_drop {
+            evt.prevent_default(); // Required to allow drop
+            evt.stop_propagation();
+            if !is_drop_target() {
+                is_drop_target.set(true);
+                if let Some(handler) = &on_divider_hover {
+                    handler.call(true);
+                }
+            }
+        }
+    };
+    let mut leave_drop_target = move || {
+        if is_drop_target() {
+            is_drop_target.set(false);
+            if let Some(handler) = &on_divider_hover {
+                handler.call(false);
+            }
+        }
+    };
+    let handle_drag_leave = move |_evt: Event<DragData>| leave_drop_target();
+    let handle_drop = move |evt: Event<DragData>| {
+        if let Some(handler) = &on_divider_drop {
+            evt.prevent_default();
+            evt.stop_propagation();
+            leave_drop_target();
+            handler.call(());
+        }
+    };
+    let divider_class = match (is_drop_target(), is_hovering()) {
+        (true, _) => "split-divider drop-target",
+        (false, true) => "split-divider hover",
+        (false, false) => "split-divider",
     };
 
     rsx! {
         div {
-            class: "split-pane",
+            class: if is_dragging() { "split-pane resizing" } else { "split-pane" },
                 // style: "margin: 0.5rem;",
-            onmounted: move |evt| {
-                spawn(async move {
-                    if let Some(element) = evt.data().downcast::<web_sys::Element>() {
-                        if let Ok(html_element) = element.clone().dyn_into::<web_sys::HtmlElement>() {
-                            container_ref.set(Some(html_element));
-                        }
-                    }
-                });
-            },
+            onmounted: move |evt| container_ref.set(Some(evt.data())),
             onmousemove: handle_mouse_move,
             onmouseup: handle_mouse_up,
             style: {
                 let current_split = split_pos();
-                let divider_width = 4.0; // Slightly wider divider for better UX
-                let gap = 6.0; // Gap between panels and divider in pixels
-
-                let grid_template = match direction {
-                    SplitDirection::Horizontal =>
-                        format!("{current_split}% {gap}px {divider_width}px {gap}px calc(100% - {current_split}% - {divider_width}px - {gap}px * 2)"),
-                    SplitDirection::Vertical =>
-                        format!("{current_split}% {gap}px {divider_width}px {gap}px calc(100% - {current_split}% - {divider_width}px - {gap}px * 2)"),
-                };
+                // Unstyled mode doesn't declare the variables, so they default to 0
+                let divider_width = "var(--mosaic-divider-width, 0px)";
+                let gap = "var(--mosaic-gap, 0px)";
+
+                // The grid template is geometry, so it is emitted even when unstyled
+                let grid_template = format!("{current_split}% {gap} {divider_width} {gap} calc(100% - {current_split}% - {divider_width} - {gap} * 2)");
                 let grid_direction = match direction {
                     SplitDirection::Horizontal => "grid-template-columns",
                     SplitDirection::Vertical => "grid-template-rows",
                 };
-                format!("
-                    display: grid;
-                    {}: {};
-                    width: 100%;
-                    height: 100%;
-                    {}
-                ", grid_direction, grid_template, if is_dragging() { "user-select: none;" } else { "" })
+                // Keep receiving the mouse over the whole pane while dragging
+                let dragging = if is_dragging() { "pointer-events: auto; user-select: none;" } else { "" };
+                if styled {
+                    format!("
+                        {}
+                        display: grid;
+                        {}: {};
+                        width: 100%;
+                        height: 100%;
+                        {}
+                    ", theme.variables, grid_direction, grid_template, dragging)
+                } else {
+                    format!("{}: {}; {}", grid_direction, grid_template, dragging)
+                }
             },
 
             // First pane
             div {
                 class: "split-pane-first",
-                style: "min-width: 0; min-height: 0;",
+                style: if styled { "min-width: 0; min-height: 0;" } else { "" },
                 {first_pane}
             }
 
             // Gap before divider (also draggable, triggers hover)
             div {
                 class: "split-gap-before",
-                onmousedown: move |_evt| {
-                    is_dragging.set(true);
-                },
+                onmousedown: move |_evt| start_drag(),
+                ondragover: handle_drag_over,
+                ondragleave: handle_drag_leave,
+                ondrop: handle_drop,
                 onmouseenter: move |_evt| {
                     is_hovering.set(true);
                 },
                 onmouseleave: move |_evt| {
                     is_hovering.set(false);
                 },
-                style: "
-                    background-color: transparent;
-                    cursor: {cursor};
-                ",
+                style: "{gap_style}",
             }
 
             // Divider (drag handle)
             div {
-                class: "split-divider",
-                onmousedown: move |_evt| {
-                    is_dragging.set(true);
-                },
+                class: divider_class,
+                onmousedown: move |_evt| start_drag(),
+                ondragover: handle_drag_over,
+                ondragleave: handle_drag_leave,
+                ondrop: handle_drop,
                 onmouseenter: move |_evt| {
                     is_hovering.set(true);
                 },
                 onmouseleave: move |_evt| {
                     is_hovering.set(false);
                 },
-                style: {
-                    let bg_color = if is_hovering() { "#3a4050" } else { "#2a2f3a" };
-                    format!("
-                        background-color: {bg_color};
-                        cursor: {cursor};
-                        transition: background-color 0.2s ease;
-                        position: relative;
-                        border-radius: 3px;
-                    ")
-                },
+                style: "{divider_style}",
 
                 // Visual indicator line
                 div {
-                    style: match direction {
-                        SplitDirection::Horizontal => {
-                            let indicator_color = if is_hovering() { "#888" } else { "#555" };
-                            format!("
-                                position: absolute;
-                                top: 50%;
-                                left: 50%;
-                                transform: translate(-50%, -50%);
-                                width: 2px;
-                                height: 30px;
-                                background-color: {indicator_color};
-                                border-radius: 2px;
-                                transition: background-color 0.2s ease;
-                            ")
-                        },
-                        SplitDirection::Vertical => {
-                            let indicator_color = if is_hovering() { "#888" } else { "#555" };
-                            format!("
-                                position: absolute;
-                                top: 50%;
-                                left: 50%;
-                                transform: translate(-50%, -50%);
-                                width: 30px;
-                                height: 2px;
-                                background-color: {indicator_color};
-                                border-radius: 2px;
-                                transition: background-color 0.2s ease;
-                            ")
-                        },
-                    }
+                    class: "split-divider-indicator",
+                    style: "{indicator_style}",
                 }
             }
 
             // Gap after divider (also draggable, triggers hover)
             div {
                 class: "split-gap-after",
-                onmousedown: move |_evt| {
-                    is_dragging.set(true);
-                },
+                onmousedown: move |_evt| start_drag(),
+                ondragover: handle_drag_over,
+                ondragleave: handle_drag_leave,
+                ondrop: handle_drop,
                 onmouseenter: move |_evt| {
                     is_hovering.set(true);
                 },
                 onmouseleave: move |_evt| {
                     is_hovering.set(false);
                 },
-                style: "
-                    background-color: transparent;
-                    cursor: {cursor};
-                ",
+                style: "{gap_style}",
             }
 
             // Second pane
             div {
                 class: "split-pane-second",
-                style: "min-width: 0; min-height: 0;",
+                style: if styled { "min-width: 0; min-height: 0;" } else { "" },
                 {second_pane}
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::dioxus_core::{ElementId, Mutation, Mutations};
+    use dioxus_liveview::LiveviewRouter;
+    use futures_util::{SinkExt, StreamExt};
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::Message;
+
+    /// Last positions reported through `on_resizing` and `on_resize`
+    static RESIZING_TO: Mutex<Option<f64>> = Mutex::new(None);
+    static RESIZED_TO: Mutex<Option<f64>> = Mutex::new(None);
+
+    fn resizable_app() -> Element {
+        rsx! {
+            SplitPane {
+                direction: SplitDirection::Horizontal,
+                initial_size: 50.0,
+                min_size: 20.0,
+                max_size: 80.0,
+                on_resizing: move |pos: f64| *RESIZING_TO.lock().unwrap() = Some(pos),
+                on_resize: move |pos: f64| *RESIZED_TO.lock().unwrap() = Some(pos),
+                first_pane: rsx! { "first" },
+                second_pane: rsx! { "second" },
+            }
+        }
+    }
+
+    /// Element IDs of the listeners the app registers, in creation order
+    ///
+    /// Element IDs are allocated deterministically, so a local render yields the
+    /// same IDs the liveview server assigns.
+    fn listener_ids(event: &str) -> Vec<ElementId> {
+        let mut dom = VirtualDom::new(resizable_app);
+        let mut mutations = Mutations::default();
+        dom.rebuild(&mut mutations);
+        mutations
+            .edits
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Mutation::NewEventListener { name, id } if name == event => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn user_event(name: &str, element: ElementId, data: serde_json::Value) -> Message {
+        let event = serde_json::json!({
+            "method": "user_event",
+            "params": { "name": name, "element": element.0, "bubbles": true, "data": data },
+        });
+        Message::text(event.to_string())
+    }
+
+    fn mouse_data(client_x: f64, client_y: f64) -> serde_json::Value {
+        serde_json::json!({
+            "alt_key": false, "button": 0, "buttons": 1,
+            "client_x": client_x, "client_y": client_y,
+            "ctrl_key": false, "meta_key": false,
+            "offset_x": 0.0, "offset_y": 0.0,
+            "page_x": client_x, "page_y": client_y,
+            "screen_x": client_x, "screen_y": client_y,
+            "shift_key": false,
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_divider_drag_over_liveview() {
+        let container = listener_ids("mounted")[0];
+        let divider = listener_ids("mousedown")[1];
+
+        // Serve the app from a local liveview server
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = axum::Router::new().with_app("/", resizable_app);
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr))
+            .await
+            .unwrap();
+
+        // Initial render
+        ws.next().await.unwrap().unwrap();
+
+        // Mount the container and grab the divider, which measures the container
+        ws.send(user_event("mounted", container, serde_json::json!({})))
+            .await
+            .unwrap();
+        ws.send(user_event("mousedown", divider, mouse_data(500.0, 10.0)))
+            .await
+            .unwrap();
+
+        // Answer the client rect query the way the browser glue would
+        let request_id = loop {
+            let frame = tokio::time::timeout(Duration::from_secs(5), ws.next())
+                .await
+                .expect("no client rect query received")
+                .unwrap()
+                .unwrap()
+                .into_data();
+            // Text frames are prefixed with 0, binary edit frames with 1
+            if frame.first() != Some(&0) {
+                continue;
+            }
+            let text = String::from_utf8_lossy(&frame[1..]).to_string();
+            if !text.contains("getClientRect") {
+                continue;
+            }
+            let id = text.split("let _request_id = ").nth(1).unwrap();
+            break id[..id.find(';').unwrap()].parse::<usize>().unwrap();
+        };
+        let rect = serde_json::json!({
+            "method": "query",
+            "params": {
+                "id": request_id,
+                "data": { "type": "GetClientRect", "origin": [0.0, 0.0], "size": [1000.0, 400.0] },
+                "returned_value": true,
+            },
+        });
+        ws.send(Message::text(rect.to_string())).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Wait until `reported` holds a position
+        let reported = |position: &'static Mutex<Option<f64>>| async move {
+            for _ in 0..50 {
+                if let Some(position) = *position.lock().unwrap() {
+                    return Some(position);
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            None
+        };
+
+        // Drag to 30% of the container width: the position is followed live...
+        ws.send(user_event("mousemove", container, mouse_data(300.0, 10.0)))
+            .await
+            .unwrap();
+        assert_eq!(reported(&RESIZING_TO).await, Some(30.0));
+        assert_eq!(*RESIZED_TO.lock().unwrap(), None);
+
+        // ...and only submitted on release
+        ws.send(user_event("mouseup", container, mouse_data(300.0, 10.0)))
+            .await
+            .unwrap();
+        assert_eq!(reported(&RESIZED_TO).await, Some(30.0));
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Errors reported by a [`LayoutStorage`] backend
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageError {
+    /// The backend cannot be reached (e.g. no `window`, storage disabled)
+    Unavailable(String),
+
+    /// The backend was reachable but the operation failed
+    Backend(String),
+
+    /// The layout could not be serialized
+    Serialization(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Unavailable(msg) => write!(f, "storage unavailable: {}", msg),
+            StorageError::Backend(msg) => write!(f, "storage operation failed: {}", msg),
+            StorageError::Serialization(msg) => write!(f, "failed to serialize layout: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+/// A key-value backend that layouts can be persisted to
+///
+/// Implementations only move strings around; versioning and migrations are
+/// handled by `MosaicLayout::save_to` and `MosaicLayout::load_from`.
+pub trait LayoutStorage {
+    /// Read the payload stored under `key`, or `None` if nothing is stored
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError>;
+
+    /// Store `value` under `key`, replacing any previous payload
+    fn write(&self, key: &str, value: &str) -> Result<(), StorageError>;
+
+    /// Remove the payload stored under `key` (no-op if nothing is stored)
+    fn remove(&self, key: &str) -> Result<(), StorageError>;
+}
+
+/// Browser `localStorage` backend
+#[cfg(feature = "web")]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LocalStorage;
+
+/// Browser `sessionStorage` backend
+#[cfg(feature = "web")]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SessionStorage;
+
+#[cfg(feature = "web")]
+fn web_storage(local: bool) -> Result<web_sys::Storage, StorageError> {
+    let name = if local {
+        "localStorage"
+    } else {
+        "sessionStorage"
+    };
+    web_sys::window()
+        .and_then(|w| {
+            if local {
+                w.local_storage().ok().flatten()
+            } else {
+                w.session_storage().ok().flatten()
+            }
+        })
+        .ok_or_else(|| StorageError::Unavailable(format!("{} not available", name)))
+}
+
+#[cfg(feature = "web")]
+impl LayoutStorage for LocalStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        web_storage(true)?
+            .get_item(key)
+            .map_err(|_| StorageError::Backend("Failed to read from localStorage".to_string()))
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        web_storage(true)?
+            .set_item(key, value)
+            .map_err(|_| StorageError::Backend("Failed to save to localStorage".to_string()))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        web_storage(true)?
+            .remove_item(key)
+            .map_err(|_| StorageError::Backend("Failed to remove from localStorage".to_string()))
+    }
+}
+
+#[cfg(feature = "web")]
+impl LayoutStorage for SessionStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        web_storage(false)?
+            .get_item(key)
+            .map_err(|_| StorageError::Backend("Failed to read from sessionStorage".to_string()))
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        web_storage(false)?
+            .set_item(key, value)
+            .map_err(|_| StorageError::Backend("Failed to save to sessionStorage".to_string()))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        web_storage(false)?
+            .remove_item(key)
+            .map_err(|_| StorageError::Backend("Failed to remove from sessionStorage".to_string()))
+    }
+}
+
+/// In-memory backend, mainly intended for tests
+///
+/// Clones share the same underlying map, so a clone handed to a component
+/// observes everything written through the original.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    entries: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl MemoryStorage {
+    /// Create an empty in-memory store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, String>>, StorageError> {
+        self.entries
+            .lock()
+            .map_err(|_| StorageError::Backend("memory storage lock poisoned".to_string()))
+    }
+}
+
+impl PartialEq for MemoryStorage {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl LayoutStorage for MemoryStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        Ok(self.entries()?.get(key).cloned())
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.entries()?.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        self.entries()?.remove(key);
+        Ok(())
+    }
+}
+
+/// Filesystem backend for native targets
+///
+/// Each key is stored as `<dir>/<key>.json`. Keys may only contain ASCII
+/// letters, digits, `-`, `_` and `.` so they can't escape the directory.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileStorage {
+    dir: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    /// Create a store rooted at `dir` (created on first write)
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path_for(&self, key: &str) -> Result<std::path::PathBuf, StorageError> {
+        let valid = !key.is_empty()
+            && !key.starts_with('.')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(StorageError::Backend(format!(
+                "invalid storage key: {:?}",
+                key
+            )));
+        }
+        Ok(self.dir.join(format!("{}.json", key)))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl LayoutStorage for FileStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        match std::fs::read_to_string(self.path_for(key)?) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(StorageError::Backend(e.to_string())),
+        }
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        std::fs::create_dir_all(&self.dir).map_err(|e| StorageError::Backend(e.to_string()))?;
+        std::fs::write(path, value).map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), StorageError> {
+        match std::fs::remove_file(self.path_for(key)?) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::Backend(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::LoadError;
+    use crate::layout::MosaicLayout;
+    use crate::types::SplitDirection;
+
+    fn sample_layout() -> MosaicLayout {
+        let mut layout = MosaicLayout::new("a".to_string());
+        layout.split_tile(
+            &"a".to_string(),
+            SplitDirection::Horizontal,
+            "b".to_string(),
+            30.0,
+        );
+        layout
+    }
+
+    #[test]
+    fn test_memory_storage_roundtrip() {
+        let storage = MemoryStorage::new();
+        let layout = sample_layout();
+
+        layout.save_to(&storage, "layout").unwrap();
+        let restored = MosaicLayout::load_from(&storage, "layout").unwrap();
+        assert_eq!(restored.get_all_tiles(), layout.get_all_tiles());
+
+        MosaicLayout::remove_from(&storage, "layout").unwrap();
+        assert_eq!(
+            MosaicLayout::load_from(&storage, "layout").unwrap_err(),
+            LoadError::NotFound
+        );
+    }
+
+    #[test]
+    fn test_memory_storage_clones_share_entries() {
+        let storage = MemoryStorage::new();
+        let clone = storage.clone();
+        storage.write("key", "value").unwrap();
+        assert_eq!(clone.read("key").unwrap(), Some("value".to_string()));
+    }
+
+    #[test]
+    fn test_file_storage_roundtrip() {
+        let dir = std::env::temp_dir().join(format!(
+            "dioxus-mosaic-test-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let storage = FileStorage::new(&dir);
+        let layout = sample_layout();
+
+        assert_eq!(
+            MosaicLayout::load_from(&storage, "layout").unwrap_err(),
+            LoadError::NotFound
+        );
+
+        layout.save_to(&storage, "layout").unwrap();
+        assert!(dir.join("layout.json").exists());
+        let restored = MosaicLayout::load_from(&storage, "layout").unwrap();
+        assert_eq!(restored.get_all_tiles(), layout.get_all_tiles());
+
+        MosaicLayout::remove_from(&storage, "layout").unwrap();
+        assert!(!dir.join("layout.json").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_file_storage_rejects_path_traversal() {
+        let storage = FileStorage::new(std::env::temp_dir());
+        assert!(storage.write("../escape", "{}").is_err());
+        assert!(storage.read("nested/key").is_err());
+    }
+}
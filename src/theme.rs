@@ -0,0 +1,242 @@
+use std::fmt::Write;
+
+/// Colors and dimensions used by the mosaic components
+///
+/// The theme is emitted as CSS custom properties (`--mosaic-*`) on the
+/// `.mosaic-container` element, and the components only reference those
+/// variables. Any of them can be overridden from a stylesheet on a descendant,
+/// e.g. `.mosaic-tile-pane { --mosaic-tile-background: white; }`.
+///
+/// With `unstyled` set, no inline styles are emitted except the geometry that
+/// depends on state (split positions, drag ghost position), so the components
+/// can be styled entirely through their class names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MosaicTheme {
+    /// Only emit class names and state-dependent geometry
+    pub unstyled: bool,
+
+    /// Tile body background
+    pub tile_background: String,
+    /// Tile and header borders
+    pub tile_border: String,
+    /// Tile header background
+    pub header_background: String,
+    /// Tile title color
+    pub header_text: String,
+    /// Secondary text (empty state, drag ghost placeholder)
+    pub muted_text: String,
+    /// Highlight color (drag ghost border)
+    pub accent: String,
+    /// Close button color
+    pub danger: String,
+    /// Border of the header buttons
+    pub control_border: String,
+
+    /// Divider background
+    pub divider: String,
+    /// Divider background while hovered
+    pub divider_hover: String,
+    /// Grip line drawn in the middle of the divider
+    pub divider_indicator: String,
+    /// Grip line while the divider is hovered
+    pub divider_indicator_hover: String,
+
+    /// Drop zone background while dragging over a tile
+    pub drop_zone_background: String,
+    /// Drop zone border while dragging over a tile
+    pub drop_zone_border: String,
+    /// Background of the drop zone under the cursor
+    pub drop_zone_active_background: String,
+    /// Border of the drop zone under the cursor
+    pub drop_zone_active_border: String,
+
+    /// Shadow under the drag ghost
+    pub shadow: String,
+
+    /// Corner radius of tiles and the drag ghost, in pixels
+    pub tile_radius: f64,
+    /// Corner radius of dividers and header buttons, in pixels
+    pub control_radius: f64,
+    /// Width of the divider between two panes, in pixels
+    pub divider_width: f64,
+    /// Gap between a pane and the divider, in pixels
+    pub gap: f64,
+    /// Minimum height of the tile header, in pixels
+    pub header_height: f64,
+}
+
+impl MosaicTheme {
+    /// Dark preset (the default)
+    pub fn dark() -> Self {
+        Self {
+            unstyled: false,
+            tile_background: "#1a1d24".to_string(),
+            tile_border: "#2a2f3a".to_string(),
+            header_background: "#14161c".to_string(),
+            header_text: "#ffffff".to_string(),
+            muted_text: "#888888".to_string(),
+            accent: "#3b82f6".to_string(),
+            danger: "#dd6666".to_string(),
+            control_border: "#3a4050".to_string(),
+            divider: "#2a2f3a".to_string(),
+            divider_hover: "#3a4050".to_string(),
+            divider_indicator: "#555555".to_string(),
+            divider_indicator_hover: "#888888".to_string(),
+            drop_zone_background: "rgba(59, 130, 246, 0.15)".to_string(),
+            drop_zone_border: "rgba(59, 130, 246, 0.4)".to_string(),
+            drop_zone_active_background: "rgba(59, 130, 246, 0.3)".to_string(),
+            drop_zone_active_border: "rgba(59, 130, 246, 0.8)".to_string(),
+            shadow: "0 8px 16px rgba(0, 0, 0, 0.3)".to_string(),
+            tile_radius: 8.0,
+            control_radius: 3.0,
+            divider_width: 4.0,
+            gap: 6.0,
+            header_height: 40.0,
+        }
+    }
+
+    /// Light preset
+    pub fn light() -> Self {
+        Self {
+            unstyled: false,
+            tile_background: "#ffffff".to_string(),
+            tile_border: "#d0d7de".to_string(),
+            header_background: "#f6f8fa".to_string(),
+            header_text: "#1f2328".to_string(),
+            muted_text: "#656d76".to_string(),
+            accent: "#0969da".to_string(),
+            danger: "#cf222e".to_string(),
+            control_border: "#d0d7de".to_string(),
+            divider: "#d0d7de".to_string(),
+            divider_hover: "#afb8c1".to_string(),
+            divider_indicator: "#8c959f".to_string(),
+            divider_indicator_hover: "#57606a".to_string(),
+            drop_zone_background: "rgba(9, 105, 218, 0.1)".to_string(),
+            drop_zone_border: "rgba(9, 105, 218, 0.4)".to_string(),
+            drop_zone_active_background: "rgba(9, 105, 218, 0.2)".to_string(),
+            drop_zone_active_border: "rgba(9, 105, 218, 0.8)".to_string(),
+            shadow: "0 8px 16px rgba(31, 35, 40, 0.15)".to_string(),
+            ..Self::dark()
+        }
+    }
+
+    /// Emit class names only, leaving all styling to the application's CSS
+    pub fn unstyled() -> Self {
+        Self {
+            unstyled: true,
+            ..Self::dark()
+        }
+    }
+
+    /// The theme as CSS custom property declarations (`--mosaic-*: value;`)
+    pub fn css_variables(&self) -> String {
+        let colors = [
+            ("tile-background", &self.tile_background),
+            ("tile-border", &self.tile_border),
+            ("header-background", &self.header_background),
+            ("header-text", &self.header_text),
+            ("muted-text", &self.muted_text),
+            ("accent", &self.accent),
+            ("danger", &self.danger),
+            ("control-border", &self.control_border),
+            ("divider", &self.divider),
+            ("divider-hover", &self.divider_hover),
+            ("divider-indicator", &self.divider_indicator),
+            ("divider-indicator-hover", &self.divider_indicator_hover),
+            ("drop-zone-background", &self.drop_zone_background),
+            ("drop-zone-border", &self.drop_zone_border),
+            (
+                "drop-zone-active-background",
+                &self.drop_zone_active_background,
+            ),
+            ("drop-zone-active-border", &self.drop_zone_active_border),
+            ("shadow", &self.shadow),
+        ];
+        let sizes = [
+            ("tile-radius", self.tile_radius),
+            ("control-radius", self.control_radius),
+            ("divider-width", self.divider_width),
+            ("gap", self.gap),
+            ("header-height", self.header_height),
+        ];
+
+        let mut css = String::new();
+        for (name, value) in colors {
+            let _ = write!(css, "--mosaic-{}: {}; ", name, value);
+        }
+        for (name, value) in sizes {
+            let _ = write!(css, "--mosaic-{}: {}px; ", name, value);
+        }
+        css
+    }
+}
+
+impl Default for MosaicTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Theme provided by `Mosaic` to the components it renders
+#[cfg(feature = "components")]
+#[derive(Clone, Copy)]
+pub(crate) struct ThemeContext(pub(crate) dioxus::prelude::Memo<MosaicTheme>);
+
+/// How a component should style itself under the current theme
+#[cfg(feature = "components")]
+pub(crate) struct ThemeStyle {
+    /// Whether cosmetic inline styles are emitted
+    pub(crate) styled: bool,
+    /// Variable declarations the component must emit itself, when it is
+    /// rendered outside of a `Mosaic` that already declares them
+    pub(crate) variables: String,
+}
+
+/// Resolve the theme for a component
+///
+/// Inside a `Mosaic` the variables are inherited from the container. Standalone
+/// components fall back to a `MosaicTheme` context, then to the dark preset.
+#[cfg(feature = "components")]
+pub(crate) fn use_theme_style() -> ThemeStyle {
+    use dioxus::prelude::*;
+
+    let mosaic_theme = try_use_context::<ThemeContext>();
+    let app_theme = try_use_context::<MosaicTheme>();
+
+    if let Some(ThemeContext(theme)) = mosaic_theme {
+        return ThemeStyle {
+            styled: !theme.read().unstyled,
+            variables: String::new(),
+        };
+    }
+
+    let theme = app_theme.unwrap_or_default();
+    ThemeStyle {
+        styled: !theme.unstyled,
+        variables: if theme.unstyled {
+            String::new()
+        } else {
+            theme.css_variables()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_variables_cover_theme() {
+        let css = MosaicTheme::light().css_variables();
+        assert!(css.contains("--mosaic-tile-background: #ffffff;"));
+        assert!(css.contains("--mosaic-divider-width: 4px;"));
+        assert!(css.contains("--mosaic-drop-zone-active-border: rgba(9, 105, 218, 0.8);"));
+    }
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(MosaicTheme::default(), MosaicTheme::dark());
+        assert!(!MosaicTheme::light().unstyled);
+        assert!(MosaicTheme::unstyled().unstyled);
+    }
+}
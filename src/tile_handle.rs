@@ -0,0 +1,330 @@
+use crate::controller::LayoutController;
+use crate::drag_drop::DragState;
+use crate::mosaic::TileRenderer;
+use crate::node::TileCapabilities;
+use crate::registry::TileRegistry;
+use crate::theme::use_theme_style;
+use crate::types::{SplitDirection, TileId};
+use dioxus::prelude::*;
+use dioxus::CapturedError;
+
+/// Tile shown on its own by `Mosaic`, if any
+#[derive(Clone, Copy)]
+pub(crate) struct MaximizedTile(pub(crate) Signal<Option<TileId>>);
+
+/// Tile that last received focus, set by `TilePane` and `TileHandle::focus`
+#[derive(Clone, Copy)]
+pub(crate) struct ActiveTile(pub(crate) Signal<Option<TileId>>);
+
+/// Tile rendered by the closest `TilePane`
+#[derive(Clone, Copy)]
+pub(crate) struct CurrentTile(pub(crate) Memo<TileId>);
+
+/// Controls for a single tile
+///
+/// Handed to `render_header` and `render_controls`, and returned by [`use_mosaic`]
+/// inside tile content.
+///
+/// # Example
+/// ```ignore
+/// let render_controls = |(_tile_id, handle): (TileId, TileHandle)| {
+///     let maximize = handle.clone();
+///     rsx! {
+///         button { onclick: move |_| maximize.toggle_maximize(), "⛶" }
+///         button { onclick: move |_| handle.close(), "✕" }
+///     }
+/// };
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct TileHandle {
+    tile_id: TileId,
+    controller: LayoutController,
+    drag_state: Signal<DragState>,
+    maximized: Option<Signal<Option<TileId>>>,
+    registry: Option<Memo<Option<TileRegistry>>>,
+    active: Option<Signal<Option<TileId>>>,
+}
+
+impl TileHandle {
+    /// Build a handle from the contexts provided by `Mosaic`
+    pub(crate) fn from_context(tile_id: TileId) -> Self {
+        Self {
+            tile_id,
+            controller: consume_context::<LayoutController>(),
+            drag_state: consume_context::<Signal<DragState>>(),
+            maximized: try_consume_context::<MaximizedTile>().map(|m| m.0),
+            registry: try_consume_context::<TileRenderer>().map(|r| r.registry),
+            active: try_consume_context::<ActiveTile>().map(|a| a.0),
+        }
+    }
+
+    /// ID of the tile this handle controls
+    pub fn tile_id(&self) -> &TileId {
+        &self.tile_id
+    }
+
+    /// Whether the tile is locked (can neither be closed nor be dropped on)
+    pub fn is_locked(&self) -> bool {
+        self.capabilities()
+            .is_some_and(|capabilities| capabilities.is_locked())
+    }
+
+    /// What users can do with the tile, or `None` once it has been closed
+    ///
+    /// Nothing is allowed in a read-only mosaic.
+    pub fn capabilities(&self) -> Option<TileCapabilities> {
+        let capabilities = self
+            .controller
+            .layout()
+            .read()
+            .tile_capabilities(&self.tile_id)?;
+        Some(if self.is_read_only() {
+            TileCapabilities::none()
+        } else {
+            capabilities
+        })
+    }
+
+    /// Whether the mosaic is in `MosaicMode::ReadOnly`
+    pub fn is_read_only(&self) -> bool {
+        self.controller.is_read_only()
+    }
+
+    /// Close the tile, promoting its sibling (no-op if it isn't closable, or
+    /// in a read-only mosaic)
+    ///
+    /// In strictly controlled mode this only proposes the change to `on_change`.
+    /// A maximized tile stays maximized until it is actually gone from the layout.
+    pub fn close(&self) {
+        if self.is_read_only() {
+            return;
+        }
+        self.controller
+            .update(|layout| layout.close_tile(&self.tile_id));
+    }
+
+    /// Split the tile, placing `new_tile_id` in the second pane
+    ///
+    /// The new tile gets the `default_size` of its kind in the registry, or half
+    /// of the space. Returns false if the tile isn't splittable, the registry
+    /// doesn't allow opening it, or the mosaic is read-only.
+    pub fn split(&self, direction: SplitDirection, new_tile_id: impl Into<TileId>) -> bool {
+        if self.is_read_only() {
+            return false;
+        }
+        let new_tile_id = new_tile_id.into();
+        let layout = self.controller.layout();
+        let registry = self.registry.and_then(|registry| registry.read().clone());
+
+        let mut new_size = 50.0;
+        if let Some(registry) = registry {
+            if !registry.can_open(&layout.read(), &new_tile_id) {
+                return false;
+            }
+            new_size = registry.size_hint(&new_tile_id).unwrap_or(new_size);
+        }
+
+        self.controller.update(|layout| {
+            layout.split_tile(&self.tile_id, direction, new_tile_id, 100.0 - new_size)
+        })
+    }
+
+    /// Whether the tile currently fills the whole mosaic
+    pub fn is_maximized(&self) -> bool {
+        self.maximized
+            .is_some_and(|maximized| maximized.read().as_ref() == Some(&self.tile_id))
+    }
+
+    /// Show the tile on its own, or restore the layout if it already is
+    pub fn toggle_maximize(&self) {
+        if let Some(mut maximized) = self.maximized {
+            let next = if self.is_maximized() {
+                None
+            } else {
+                Some(self.tile_id.clone())
+            };
+            maximized.set(next);
+        }
+    }
+
+    /// Make this the active tile of the mosaic
+    pub fn focus(&self) {
+        if let Some(mut active) = self.active {
+            if active.peek().as_ref() != Some(&self.tile_id) {
+                active.set(Some(self.tile_id.clone()));
+            }
+        }
+    }
+
+    /// Whether this is the active tile (the one last clicked or focused)
+    pub fn is_active(&self) -> bool {
+        self.active
+            .is_some_and(|active| active.read().as_ref() == Some(&self.tile_id))
+    }
+
+    /// Share of the mosaic taken by the tile, as `(width, height)` percentages
+    ///
+    /// Derived from the layout's split percentages, so it updates as soon as a
+    /// divider moves. Returns `None` once the tile has been closed.
+    pub fn size(&self) -> Option<(f64, f64)> {
+        self.controller.layout().read().tile_size(&self.tile_id)
+    }
+
+    /// Start dragging the tile from the given client coordinates
+    ///
+    /// Call this from the `ondragstart` handler of a draggable element, or
+    /// simply wrap the element in a [`DragHandle`]. Does nothing if the tile
+    /// isn't draggable or the mosaic is read-only.
+    pub fn start_drag(&self, x: f64, y: f64) {
+        if !self
+            .capabilities()
+            .is_some_and(|capabilities| capabilities.draggable)
+        {
+            return;
+        }
+        let mut drag_state = self.drag_state;
+        drag_state
+            .write()
+            .start_tile_drag(self.controller, self.tile_id.clone(), x, y);
+    }
+}
+
+/// Error raised while rendering a tile's content, handed to `render_error`
+///
+/// Covers errors returned from the content (e.g. with `?`), and panics on
+/// targets where they unwind (not wasm, which aborts).
+///
+/// # Example
+/// ```ignore
+/// let render_error = |error: TileError| {
+///     let reload = error.clone();
+///     rsx! {
+///         p { "{error.tile_id()} failed: {error.error()}" }
+///         button { onclick: move |_| reload.reload(), "Reload" }
+///         button { onclick: move |_| error.close(), "Close" }
+///     }
+/// };
+/// ```
+#[derive(Clone)]
+pub struct TileError {
+    handle: TileHandle,
+    error: CapturedError,
+    context: ErrorContext,
+}
+
+impl PartialEq for TileError {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle && self.context == other.context
+    }
+}
+
+impl TileError {
+    pub(crate) fn new(handle: TileHandle, context: ErrorContext) -> Option<Self> {
+        let error = context.error()?;
+        Some(Self {
+            handle,
+            error,
+            context,
+        })
+    }
+
+    /// ID of the tile that failed
+    pub fn tile_id(&self) -> &TileId {
+        self.handle.tile_id()
+    }
+
+    /// What went wrong
+    pub fn error(&self) -> &CapturedError {
+        &self.error
+    }
+
+    /// Handle of the failed tile
+    pub fn handle(&self) -> &TileHandle {
+        &self.handle
+    }
+
+    /// Render the tile's content again, from a fresh state
+    pub fn reload(&self) {
+        self.context.clear_errors();
+    }
+
+    /// Close the tile (no-op if it isn't closable)
+    pub fn close(&self) {
+        self.handle.close();
+    }
+}
+
+/// Control handle for the tile the calling component is rendered in
+///
+/// Must be called from a component rendered inside a tile of a `Mosaic`
+/// (e.g. from `render_tile` or a `TileRegistry` kind).
+///
+/// # Example
+/// ```ignore
+/// #[component]
+/// fn Editor() -> Element {
+///     let tile = use_mosaic();
+///     let close = tile.clone();
+///     rsx! {
+///         button { onclick: move |_| close.close(), "Close" }
+///         button { onclick: move |_| { tile.split(SplitDirection::Vertical, "terminal:2"); }, "Open terminal" }
+///     }
+/// }
+/// ```
+pub fn use_mosaic() -> TileHandle {
+    let tile = use_context::<CurrentTile>();
+    TileHandle::from_context(tile.0())
+}
+
+/// Marks its children as the drag handle of the surrounding tile
+///
+/// Meant for headerless tiles (`render_header` returning `None`), but can be
+/// used anywhere inside a tile rendered by `Mosaic`. Tiles that aren't
+/// `draggable`, and those of a read-only mosaic, can't be dragged from it.
+#[component]
+pub fn DragHandle(children: Element) -> Element {
+    let tile = use_context::<CurrentTile>();
+    let controller = use_context::<LayoutController>();
+    let mut drag_state = use_context::<Signal<DragState>>();
+    let theme = use_theme_style();
+    let draggable = !controller.is_read_only()
+        && controller
+            .layout()
+            .read()
+            .tile_capabilities(&tile.0())
+            .is_some_and(|capabilities| capabilities.draggable);
+
+    let cursor = match (draggable, drag_state.read().is_dragging()) {
+        (false, _) => "default",
+        (true, true) => "grabbing",
+        (true, false) => "grab",
+    };
+
+    rsx! {
+        div {
+            class: "mosaic-drag-handle",
+            draggable: if draggable { "true" } else { "false" },
+            ondragstart: move |evt| {
+                if !draggable {
+                    evt.prevent_default();
+                    return;
+                }
+                let position = evt.client_coordinates();
+                drag_state.write().start_tile_drag(controller, tile.0(), position.x, position.y);
+            },
+            ondrag: move |evt| {
+                let position = evt.client_coordinates();
+
+                // Drag events can fire at 0,0 when the drag ends
+                if position.x != 0.0 || position.y != 0.0 {
+                    drag_state.write().update_position(position.x, position.y);
+                }
+            },
+            ondragend: move |_evt| {
+                drag_state.write().end_drag();
+            },
+            style: if theme.styled { format!("cursor: {cursor}; user-select: none;") } else { String::new() },
+            {children}
+        }
+    }
+}
@@ -1,36 +1,65 @@
-use crate::drag_drop::{calculate_drop_zone, get_drop_zone_style, DragState, DropZone};
-use crate::layout::MosaicLayout;
+use crate::controller::LayoutController;
+use crate::drag_drop::{drop_payload, get_drop_zone_style, zone_name, DragState};
+use crate::drop_zone::{DropZone, DropZoneGeometry};
+use crate::mosaic::TileRenderer;
+use crate::node::TileCapabilities;
+use crate::theme::use_theme_style;
+use crate::tile_handle::{ActiveTile, CurrentTile};
 use crate::types::TileId;
+use dioxus::html::geometry::PixelsRect;
 use dioxus::prelude::*;
-use wasm_bindgen::JsCast;
-use web_sys::HtmlElement;
+use std::rc::Rc;
 
-/// Wrapper for a tile with controls (split, close, drag-drop)
+/// Wrapper for a tile with controls (close, drag-drop)
 ///
 /// This component provides the UI controls for managing a tile,
-/// including splitting it horizontally/vertically, closing it, and drag-and-drop reordering.
+/// including closing it and drag-and-drop reordering. Tiles are split through
+/// `TileHandle::split`, e.g. from custom `controls`.
 #[component]
 pub fn TilePane(
     tile_id: TileId,
     title_component: Element,
-    locked: bool,
-    on_split_horizontal: EventHandler<()>,
-    on_split_vertical: EventHandler<()>,
+    /// What users can do with the tile: close it, drag it, drop on it
+    #[props(default)]
+    capabilities: TileCapabilities,
     on_close: EventHandler<()>,
+    /// Render the header (default), or leave it out for a headerless tile
+    #[props(default = true)]
+    show_header: bool,
+    /// Replaces the title and controls inside the header
+    header: Option<Element>,
+    /// Replaces the default controls (close button) on the right of the header
+    controls: Option<Element>,
+    /// How the tile is divided into drop zones
+    #[props(default)]
+    drop_zones: DropZoneGeometry,
     children: Element,
 ) -> Element {
     // Get drag state and layout from context
     let mut drag_state = use_context::<Signal<DragState>>();
-    let mut layout = use_context::<Signal<MosaicLayout>>();
+    let controller = use_context::<LayoutController>();
+    let renderer = try_use_context::<TileRenderer>();
 
-    // Track element reference for drop zone calculation
-    let mut tile_ref = use_signal(|| None::<HtmlElement>);
+    // Track element reference and bounds for drop zone calculation
+    let mut tile_ref = use_signal(|| None::<Rc<MountedData>>);
+    let mut tile_rect = use_signal(|| None::<PixelsRect>);
+
+    // Measure the tile when a drag enters it (works on every renderer)
+    let measure_tile = move || {
+        if let Some(tile) = tile_ref() {
+            spawn(async move {
+                if let Ok(rect) = tile.get_client_rect().await {
+                    tile_rect.set(Some(rect));
+                }
+            });
+        }
+    };
 
     // Track current drop zone when this tile is hovered during drag
     let mut current_drop_zone = use_signal(|| None::<DropZone>);
 
-    // Check if this tile is currently being dragged
-    let is_being_dragged = drag_state.read().dragging_tile_id.as_ref() == Some(&tile_id);
+    // Check if this tile is currently being dragged, alone or with its split
+    let is_being_dragged = drag_state.read().drags_tile(controller, &tile_id);
 
     // Check if drag is active and this tile is being hovered
     let is_drag_active = drag_state.read().is_dragging();
@@ -38,46 +67,103 @@ pub fn TilePane(
     let tile_opacity = if is_being_dragged { "0.4" } else { "1.0" };
 
     // Pre-calculate cursor style
-    let header_cursor = if is_drag_active { "grabbing" } else { "grab" };
+    let draggable = capabilities.draggable;
+    let closable = capabilities.closable;
+    let header_cursor = match (draggable, is_drag_active) {
+        (false, _) => "default",
+        (true, true) => "grabbing",
+        (true, false) => "grab",
+    };
+
+    let theme = use_theme_style();
+    let styled = theme.styled;
+
+    // Let `DragHandle` and other descendants know which tile they belong to
+    let current_tile = use_memo(use_reactive((&tile_id,), |(tile_id,)| tile_id));
+    use_context_provider(|| CurrentTile(current_tile));
+
+    // Clicking or focusing anything inside the tile makes it the active one
+    let active_tile = try_use_context::<ActiveTile>();
+    let is_active = active_tile.is_some_and(|active| active.0.read().as_ref() == Some(&tile_id));
+    let activate = {
+        let tile_id = tile_id.clone();
+        move || {
+            if let Some(ActiveTile(mut active)) = active_tile {
+                if active.peek().as_ref() != Some(&tile_id) {
+                    active.set(Some(tile_id.clone()));
+                }
+            }
+        }
+    };
+    let activate_on_focus = activate.clone();
+    let activate_on_click = activate;
+
+    let pane_class = match (is_being_dragged, is_active) {
+        (true, _) => "mosaic-tile-pane dragging",
+        (false, true) => "mosaic-tile-pane active",
+        (false, false) => "mosaic-tile-pane",
+    };
+
+    // Zones of this tile where the dragged payload may be dropped
+    let can_drop_at = {
+        let tile_id = tile_id.clone();
+        move |zone: DropZone| {
+            capabilities.drop_target
+                && drag_state
+                    .read()
+                    .payload_for(controller)
+                    .is_some_and(|payload| controller.can_drop(payload, Some(&tile_id), zone))
+        }
+    };
+    let allowed_zones: Vec<DropZone> =
+        if is_drag_active && !is_being_dragged && capabilities.drop_target {
+            [
+                DropZone::Top,
+                DropZone::Bottom,
+                DropZone::Left,
+                DropZone::Right,
+                DropZone::Center,
+            ]
+            .into_iter()
+            .filter(|zone| *zone != DropZone::Center || drop_zones.has_center())
+            .filter(|zone| can_drop_at(*zone))
+            .collect()
+        } else {
+            Vec::new()
+        };
 
     let tile_id_ondrop = tile_id.clone();
     let tile_id_ondragover = tile_id.clone();
+    let tile_id_ondragleave = tile_id.clone();
 
     rsx! {
         div {
-            class: "mosaic-tile-pane",
-            onmounted: move |evt| {
-                spawn(async move {
-                    if let Some(element) = evt.data().downcast::<web_sys::Element>() {
-                        if let Ok(html_element) = element.clone().dyn_into::<HtmlElement>() {
-                            tile_ref.set(Some(html_element));
-                        }
-                    }
-                });
-            },
+            class: pane_class,
+            onmounted: move |evt| tile_ref.set(Some(evt.data())),
+            onmousedown: move |_evt| activate_on_click(),
+            onfocusin: move |_evt| activate_on_focus(),
+            ondragenter: move |_evt| measure_tile(),
             // Handle drag over for drop zone detection
             ondragover: move |evt| {
                 evt.prevent_default(); // Required to allow drop
 
                 // Don't allow dropping on itself
-                let current_dragging = drag_state.read().dragging_tile_id.clone();
-                if current_dragging.as_ref() == Some(&tile_id_ondragover) {
+                if drag_state.read().drags_tile(controller, &tile_id_ondragover) {
                     return;
                 }
 
-                if let Some(tile_element) = tile_ref() {
-                    let rect = tile_element.get_bounding_client_rect();
-                    let mouse_x = evt.page_coordinates().x as f64;
-                    let mouse_y = evt.page_coordinates().y as f64;
+                if let Some(rect) = tile_rect() {
+                    let mouse_x = evt.client_coordinates().x;
+                    let mouse_y = evt.client_coordinates().y;
 
-                    if let Some(zone) = calculate_drop_zone(
+                    if let Some(zone) = drop_zones.zone_at_point(
                         mouse_x,
                         mouse_y,
-                        rect.left(),
-                        rect.top(),
-                        rect.width(),
-                        rect.height(),
-                    ) {
+                        rect.origin.x,
+                        rect.origin.y,
+                        rect.size.width,
+                        rect.size.height,
+                    ).filter(|zone| can_drop_at(*zone)) {
                         current_drop_zone.set(Some(zone));
                         drag_state.write().update_hover(tile_id_ondragover.clone(), zone);
                     } else {
@@ -88,13 +174,18 @@ pub fn TilePane(
             },
             ondragleave: move |_evt| {
                 current_drop_zone.set(None);
+                // Re-set by `ondragover` if the cursor only moved to a child element
+                let hovering_this = drag_state.read().hover_target.as_ref().is_some_and(|(hovered, _)| *hovered == tile_id_ondragleave);
+                if hovering_this {
+                    drag_state.write().clear_hover();
+                }
             },
             ondrop: move |evt| {
                 evt.prevent_default();
 
-                // Get the dragged tile ID and drop zone
-                let dragged_tile = match drag_state.read().dragging_tile_id.clone() {
-                    Some(tid) => tid,
+                // Get the dragged payload and drop zone
+                let payload = match drag_state.read().payload.clone() {
+                    Some(payload) => payload,
                     None => return,
                 };
 
@@ -104,138 +195,136 @@ pub fn TilePane(
                 };
 
                 // Don't drop on itself
-                if dragged_tile == tile_id_ondrop {
+                if drag_state.read().drags_tile(controller, &tile_id_ondrop) {
                     return;
                 }
 
                 // Perform the layout mutation
-                let success = layout.write().insert_tile_with_split(&dragged_tile, &tile_id_ondrop, zone);
-
-                if success {
-                    web_sys::console::log_1(&format!("Successfully dropped {} on {} at {:?}", dragged_tile, tile_id_ondrop, zone).into());
-                } else {
-                    web_sys::console::log_1(&format!("Failed to drop {} on {} at {:?}", dragged_tile, tile_id_ondrop, zone).into());
-                }
+                let registry = renderer.and_then(|renderer| renderer.registry.read().clone());
+                let source = drag_state.read().source;
+                drop_payload(controller, registry, &payload, source, Some(&tile_id_ondrop), zone);
 
                 // Clear drag state
                 drag_state.write().end_drag();
                 current_drop_zone.set(None);
             },
-            style: "
-                background-color: #1a1d24;
-                border: 1px solid #2a2f3a;
-                border-radius: 8px;
-                overflow: hidden;
-                display: flex;
-                flex-direction: column;
-                height: 100%;
-                position: relative;
-                opacity: {tile_opacity};
-                transition: opacity 0.2s ease;
-            ",
-
-            // Tile header with controls (draggable)
-            div {
-                class: "mosaic-tile-header",
-                draggable: "true",
-                ondragstart: move |evt| {
-                    let mouse_x = evt.page_coordinates().x as f64;
-                    let mouse_y = evt.page_coordinates().y as f64;
-                    drag_state.write().start_drag(tile_id.clone(), mouse_x, mouse_y);
-                },
-                ondragend: move |_evt| {
-                    drag_state.write().end_drag();
-                    current_drop_zone.set(None);
-                },
-                ondrag: move |evt| {
-                    let mouse_x = evt.page_coordinates().x as f64;
-                    let mouse_y = evt.page_coordinates().y as f64;
-
-                    // Only update if position actually changed (drag events can fire at 0,0)
-                    if mouse_x != 0.0 || mouse_y != 0.0 {
-                        drag_state.write().update_position(mouse_x, mouse_y);
-                    }
-                },
-                style: "
+            style: if styled {
+                format!("
+                    {}
+                    background-color: var(--mosaic-tile-background);
+                    border: 1px solid var(--mosaic-tile-border);
+                    border-radius: var(--mosaic-tile-radius);
+                    overflow: hidden;
                     display: flex;
-                    justify-content: space-between;
-                    align-items: center;
-                    padding: 0.5rem 0.75rem;
-                    border-bottom: 1px solid #2a2f3a;
-                    background-color: #14161c;
-                    flex-shrink: 0;
-                    cursor: {header_cursor};
-                    user-select: none;
-                ",
+                    flex-direction: column;
+                    height: 100%;
+                    position: relative;
+                    opacity: {tile_opacity};
+                    transition: opacity 0.2s ease;
+                ", theme.variables)
+            } else {
+                String::new()
+            },
 
-                // Title
+            // Tile header with controls (draggable), unless the tile is headerless
+            if show_header {
                 div {
-                    style: "
-                        font-size: 0.875rem;
-                        font-weight: 600;
-                        color: #ffffff;
-                        margin: 0;
-                        flex: 1;
-                        pointer-events: none;
-                    ",
-                    {title_component}
-                }
+                    class: "mosaic-tile-header",
+                    draggable: if draggable { "true" } else { "false" },
+                    ondragstart: move |evt| {
+                        if !draggable {
+                            evt.prevent_default();
+                            return;
+                        }
+                        let mouse_x = evt.client_coordinates().x;
+                        let mouse_y = evt.client_coordinates().y;
+                        drag_state.write().start_tile_drag(controller, tile_id.clone(), mouse_x, mouse_y);
+                    },
+                    ondragend: move |_evt| {
+                        drag_state.write().end_drag();
+                        current_drop_zone.set(None);
+                    },
+                    ondrag: move |evt| {
+                        let mouse_x = evt.client_coordinates().x;
+                        let mouse_y = evt.client_coordinates().y;
 
-                // Controls
-                div {
-                    class: "mosaic-tile-controls",
-                    style: "display: flex; gap: 0.25rem; align-items: center;",
+                        // Only update if position actually changed (drag events can fire at 0,0)
+                        if mouse_x != 0.0 || mouse_y != 0.0 {
+                            drag_state.write().update_position(mouse_x, mouse_y);
+                        }
+                    },
+                    style: if styled {
+                        format!("
+                            display: flex;
+                            justify-content: space-between;
+                            align-items: center;
+                            box-sizing: border-box;
+                            min-height: var(--mosaic-header-height);
+                            padding: 0.5rem 0.75rem;
+                            border-bottom: 1px solid var(--mosaic-tile-border);
+                            background-color: var(--mosaic-header-background);
+                            flex-shrink: 0;
+                            cursor: {header_cursor};
+                            user-select: none;
+                        ")
+                    } else {
+                        String::new()
+                    },
 
-                    // Split horizontal button - TEMPORARILY DISABLED
-                    // button {
-                    //     onclick: move |_| on_split_horizontal.call(()),
-                    //     title: "Split horizontally",
-                    //     style: "
-                    //         background: none;
-                    //         border: 1px solid #3a4050;
-                    //         color: #888;
-                    //         cursor: pointer;
-                    //         font-size: 0.75rem;
-                    //         padding: 0.25rem 0.5rem;
-                    //         border-radius: 3px;
-                    //         transition: all 0.2s ease;
-                    //     ",
-                    //     "⬌"
-                    // }
+                    if let Some(header) = header {
+                        // Custom header replacing the title and controls
+                        {header}
+                    } else {
+                        // Title
+                        div {
+                            class: "mosaic-tile-title",
+                            style: if styled {
+                                "
+                                font-size: 0.875rem;
+                                font-weight: 600;
+                                color: var(--mosaic-header-text);
+                                margin: 0;
+                                flex: 1;
+                                pointer-events: none;
+                                "
+                            } else {
+                                ""
+                            },
+                            {title_component}
+                        }
 
-                    // Split vertical button - TEMPORARILY DISABLED
-                    // button {
-                    //     onclick: move |_| on_split_vertical.call(()),
-                    //     title: "Split vertically",
-                    //     style: "
-                    //         background: none;
-                    //         border: 1px solid #3a4050;
-                    //         color: #888;
-                    //         cursor: pointer;
-                    //         font-size: 0.75rem;
-                    //         padding: 0.25rem 0.5rem;
-                    //         border-radius: 3px;
-                    //         transition: all 0.2s ease;
-                    //     ",
-                    //     "⬍"
-                    // }
+                        // Controls
+                        div {
+                            class: "mosaic-tile-controls",
+                            style: if styled { "display: flex; gap: 0.25rem; align-items: center;" } else { "" },
 
-                    // Close button (only if not locked)
-                    if !locked {
-                        button {
-                            onclick: move |_| on_close.call(()),
-                            title: "Close",
-                            style: "
-                                background: none;
-                                border: 1px solid #3a4050;
-                                color: #d66;
-                                cursor: pointer;
-                                font-size: 0.75rem;
-                                padding: 0.25rem 0.5rem;
-                                border-radius: 3px;
-                                transition: all 0.2s ease;
-                            ",
-                            "✕"
+                            if let Some(controls) = controls {
+                                {controls}
+                            } else {
+                                // Close button (only if the tile can be closed)
+                                if closable {
+                                    button {
+                                        onclick: move |_| on_close.call(()),
+                                        class: "mosaic-tile-close",
+                                        title: "Close",
+                                        style: if styled {
+                                            "
+                                            background: none;
+                                            border: 1px solid var(--mosaic-control-border);
+                                            color: var(--mosaic-danger);
+                                            cursor: pointer;
+                                            font-size: 0.75rem;
+                                            padding: 0.25rem 0.5rem;
+                                            border-radius: var(--mosaic-control-radius);
+                                            transition: all 0.2s ease;
+                                            "
+                                        } else {
+                                            ""
+                                        },
+                                        "✕"
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -244,52 +333,44 @@ pub fn TilePane(
             // Tile content
             div {
                 class: "mosaic-tile-content",
-                style: "
+                style: if styled {
+                    "
                     flex: 1;
                     overflow: auto;
                     min-height: 0;
-                ",
+                    "
+                } else {
+                    ""
+                },
                 {children}
             }
 
-            // Drop zone overlays (only show when drag is active and hovering over this tile)
+            // Drop zone overlays (only show when drag is active and hovering over this tile),
+            // leaving out the zones where the dragged tile can't be dropped
             if is_drag_active && !is_being_dragged {
-                // Top drop zone
-                div {
-                    class: "drop-zone drop-zone-top",
-                    style: get_drop_zone_style(
-                        DropZone::Top,
-                        current_drop_zone() == Some(DropZone::Top)
-                    ),
-                }
-
-                // Bottom drop zone
-                div {
-                    class: "drop-zone drop-zone-bottom",
-                    style: get_drop_zone_style(
-                        DropZone::Bottom,
-                        current_drop_zone() == Some(DropZone::Bottom)
-                    ),
-                }
-
-                // Left drop zone
-                div {
-                    class: "drop-zone drop-zone-left",
-                    style: get_drop_zone_style(
-                        DropZone::Left,
-                        current_drop_zone() == Some(DropZone::Left)
-                    ),
-                }
-
-                // Right drop zone
-                div {
-                    class: "drop-zone drop-zone-right",
-                    style: get_drop_zone_style(
-                        DropZone::Right,
-                        current_drop_zone() == Some(DropZone::Right)
-                    ),
+                for zone in allowed_zones {
+                    div {
+                        key: "{zone:?}",
+                        class: drop_zone_class(zone, current_drop_zone()),
+                        // Geometry, so it is emitted even when unstyled
+                        style: if styled {
+                            get_drop_zone_style(zone, current_drop_zone() == Some(zone), &drop_zones)
+                        } else {
+                            format!("position: absolute; {}", drop_zones.zone_css(zone))
+                        },
+                    }
                 }
             }
         }
     }
 }
+
+/// Class names of a drop zone overlay, marking the one under the cursor as `active`
+fn drop_zone_class(zone: DropZone, current: Option<DropZone>) -> String {
+    let name = zone_name(zone);
+    if current == Some(zone) {
+        format!("drop-zone drop-zone-{} active", name)
+    } else {
+        format!("drop-zone drop-zone-{}", name)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::layout::MosaicLayout;
-use crate::node::Node;
+use crate::node::{Node, TileCapabilities};
 use crate::types::{NodeId, SplitDirection, TileId};
 use serde::{Deserialize, Serialize};
 
@@ -7,7 +7,10 @@ use serde::{Deserialize, Serialize};
 ///
 /// This provides a simple, tree-like structure for defining layouts,
 /// which is then converted to the internal HashMap representation.
+///
+/// New kinds of nodes may be added, so matches need a wildcard arm.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum MosaicNode {
     /// A split containing two child nodes
     Split {
@@ -18,6 +21,11 @@ pub enum MosaicNode {
     },
     /// A leaf node containing a tile
     Leaf(TileId),
+    /// A leaf node containing a tile with non-default capabilities
+    Tile {
+        tile_id: TileId,
+        capabilities: TileCapabilities,
+    },
 }
 
 impl MosaicNode {
@@ -45,11 +53,28 @@ impl MosaicNode {
     pub fn tile(tile_id: impl Into<TileId>) -> Self {
         MosaicNode::Leaf(tile_id.into())
     }
-}
 
-/// Helper function for creating leaf tiles
-pub fn tile(tile_id: impl Into<TileId>) -> MosaicNode {
-    MosaicNode::tile(tile_id)
+    /// Set what users can do with this tile (no-op on splits)
+    ///
+    /// # Example
+    /// ```
+    /// # use dioxus_mosaic::{MosaicNode, TileCapabilities};
+    /// let sidebar = MosaicNode::tile("sidebar").with_capabilities(TileCapabilities::default().draggable(false));
+    /// ```
+    pub fn with_capabilities(self, capabilities: TileCapabilities) -> Self {
+        match self {
+            MosaicNode::Leaf(tile_id) | MosaicNode::Tile { tile_id, .. } => MosaicNode::Tile {
+                tile_id,
+                capabilities,
+            },
+            split => split,
+        }
+    }
+
+    /// Lock this tile, so it can't be closed nor be dropped on (no-op on splits)
+    pub fn locked(self) -> Self {
+        self.with_capabilities(TileCapabilities::locked())
+    }
 }
 
 impl MosaicLayout {
@@ -57,13 +82,13 @@ impl MosaicLayout {
     pub fn from_tree(tree: MosaicNode) -> Self {
         let mut layout = match &tree {
             MosaicNode::Leaf(tile_id) => MosaicLayout::new(tile_id.clone()),
-            MosaicNode::Split { .. } => {
+            MosaicNode::Split { .. } | MosaicNode::Tile { .. } => {
                 // Start with a dummy tile, we'll replace it
                 MosaicLayout::new("__temp__".to_string())
             }
         };
 
-        if let MosaicNode::Split { .. } = tree {
+        if !matches!(tree, MosaicNode::Leaf(_)) {
             let new_root = layout.insert_tree_recursive(&tree, None);
             layout.set_root(new_root);
             // Remove the temp tile
@@ -81,16 +106,12 @@ impl MosaicLayout {
     ) -> String {
         match tree_node {
             MosaicNode::Leaf(tile_id) => {
-                let node_id = self.gen_id();
-                let node = Node::Tile {
-                    id: node_id.clone(),
-                    tile_id: tile_id.clone(),
-                    parent: parent_id,
-                    locked: false,
-                };
-                self.insert_node(node_id.clone(), node);
-                node_id
+                self.insert_tile_node(tile_id, TileCapabilities::default(), parent_id)
             }
+            MosaicNode::Tile {
+                tile_id,
+                capabilities,
+            } => self.insert_tile_node(tile_id, *capabilities, parent_id),
             MosaicNode::Split {
                 direction,
                 first,
@@ -120,6 +141,24 @@ impl MosaicLayout {
         }
     }
 
+    /// Insert a tile node and return its ID
+    fn insert_tile_node(
+        &mut self,
+        tile_id: &TileId,
+        capabilities: TileCapabilities,
+        parent_id: Option<String>,
+    ) -> String {
+        let node_id = self.gen_id();
+        let node = Node::Tile {
+            id: node_id.clone(),
+            tile_id: tile_id.clone(),
+            parent: parent_id,
+            capabilities,
+        };
+        self.insert_node(node_id.clone(), node);
+        node_id
+    }
+
     /// Convert layout to tree representation
     /// Returns None if the layout is empty
     pub fn to_tree(&self) -> Option<MosaicNode> {
@@ -129,7 +168,17 @@ impl MosaicLayout {
     /// Recursively convert a node to tree representation
     fn node_to_tree(&self, node_id: &NodeId) -> MosaicNode {
         match self.get_node(node_id) {
-            Some(Node::Tile { tile_id, .. }) => MosaicNode::Leaf(tile_id.clone()),
+            Some(Node::Tile {
+                tile_id,
+                capabilities,
+                ..
+            }) => {
+                if *capabilities == TileCapabilities::default() {
+                    MosaicNode::Leaf(tile_id.clone())
+                } else {
+                    MosaicNode::tile(tile_id.clone()).with_capabilities(*capabilities)
+                }
+            }
             Some(Node::Split {
                 direction,
                 first,
@@ -137,13 +186,13 @@ impl MosaicLayout {
                 split_percentage,
                 ..
             }) => {
-                let first_tree = self.node_to_tree(&first);
-                let second_tree = self.node_to_tree(&second);
+                let first_tree = self.node_to_tree(first);
+                let second_tree = self.node_to_tree(second);
                 MosaicNode::Split {
-                    direction: direction.clone(),
+                    direction: *direction,
                     first: Box::new(first_tree),
                     second: Box::new(second_tree),
-                    split_percentage: split_percentage.clone(),
+                    split_percentage: *split_percentage,
                 }
             }
             None => MosaicNode::Leaf("error".to_string()),
@@ -164,11 +213,8 @@ mod tests {
 
     #[test]
     fn test_tree_to_layout_split() {
-        let tree = MosaicNode::horizontal(
-            MosaicNode::tile("tile1"),
-            MosaicNode::tile("tile2"),
-            50.0,
-        );
+        let tree =
+            MosaicNode::horizontal(MosaicNode::tile("tile1"), MosaicNode::tile("tile2"), 50.0);
         let layout = MosaicLayout::from_tree(tree);
         assert_eq!(
             layout.get_all_tiles(),
@@ -180,11 +226,7 @@ mod tests {
     fn test_tree_to_layout_nested() {
         let tree = MosaicNode::horizontal(
             MosaicNode::tile("tile1"),
-            MosaicNode::vertical(
-                MosaicNode::tile("tile2"),
-                MosaicNode::tile("tile3"),
-                60.0,
-            ),
+            MosaicNode::vertical(MosaicNode::tile("tile2"), MosaicNode::tile("tile3"), 60.0),
             40.0,
         );
         let layout = MosaicLayout::from_tree(tree);
@@ -200,11 +242,8 @@ mod tests {
 
     #[test]
     fn test_layout_to_tree() {
-        let tree = MosaicNode::horizontal(
-            MosaicNode::tile("tile1"),
-            MosaicNode::tile("tile2"),
-            50.0,
-        );
+        let tree =
+            MosaicNode::horizontal(MosaicNode::tile("tile1"), MosaicNode::tile("tile2"), 50.0);
         let layout = MosaicLayout::from_tree(tree.clone());
         let tree2 = layout.to_tree().expect("Layout should not be empty");
 
@@ -213,6 +252,37 @@ mod tests {
         assert_eq!(layout.get_all_tiles(), layout2.get_all_tiles());
     }
 
+    #[test]
+    fn test_tile_capabilities_roundtrip() {
+        let pinned = TileCapabilities::default()
+            .draggable(false)
+            .resizable(false);
+        let tree = MosaicNode::horizontal(
+            MosaicNode::tile("tile1").locked(),
+            MosaicNode::tile("tile2").with_capabilities(pinned),
+            50.0,
+        );
+        let layout = MosaicLayout::from_tree(tree);
+        assert_eq!(
+            layout.tile_capabilities(&"tile1".to_string()),
+            Some(TileCapabilities::locked())
+        );
+        assert_eq!(layout.tile_capabilities(&"tile2".to_string()), Some(pinned));
+
+        // Default capabilities come back as plain leaves
+        let mut layout = MosaicLayout::from_tree(layout.to_tree().unwrap());
+        layout.set_tile_locked(&"tile1".to_string(), false);
+        match layout.to_tree() {
+            Some(MosaicNode::Split { first, second, .. }) => {
+                assert!(matches!(*first, MosaicNode::Leaf(_)));
+                assert!(
+                    matches!(*second, MosaicNode::Tile { capabilities, .. } if capabilities == pinned)
+                );
+            }
+            tree => panic!("unexpected tree {:?}", tree),
+        }
+    }
+
     #[test]
     fn test_empty_layout_to_tree() {
         let layout = MosaicLayout::empty();
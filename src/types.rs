@@ -24,3 +24,21 @@ impl SplitDirection {
         }
     }
 }
+
+/// How users can interact with a mosaic
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MosaicMode {
+    /// Tiles can be resized, dragged, dropped and closed (within their capabilities)
+    #[default]
+    Edit,
+    /// Presentation only: the layout is shown as is, but still follows
+    /// programmatic changes
+    ReadOnly,
+}
+
+impl MosaicMode {
+    /// Whether users can't change the layout
+    pub fn is_read_only(&self) -> bool {
+        *self == MosaicMode::ReadOnly
+    }
+}